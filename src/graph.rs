@@ -1,12 +1,19 @@
 pub mod approximated_td;
+pub mod branch_and_reduce;
 pub mod builder;
 pub mod dcel;
 pub mod dcel_file_writer;
+pub mod dominating_set;
 pub mod dyn_table;
+pub mod graph_file_reader;
+pub mod graph_file_writer;
+pub mod heuristics;
 pub mod iterators;
 pub mod mis_finder;
 pub mod nice_tree_decomp;
+pub mod ntd_problem;
 pub mod node_relations;
+pub mod planarity;
 pub mod quick_graph;
 pub(crate) mod reducible;
 pub(crate) mod reductions;
@@ -61,6 +61,14 @@ impl<'a> ApproximatedTD<'a> {
     pub fn root_bag(&self) -> usize {
         self.root_bag
     }
+
+    /// Returns all vertices that are contained in at least one bag, sorted by index.
+    pub fn vertices(&self) -> Vec<VertexId> {
+        let mut vertices: Vec<VertexId> = self.bags.iter().flatten().copied().collect();
+        vertices.sort_unstable();
+        vertices.dedup();
+        vertices
+    }
 }
 
 pub struct TDBuilder<'a> {
@@ -100,7 +108,12 @@ impl<'a> TDBuilder<'a> {
             if self.tree_path_calculated[current] {
                 break;
             }
-            let prev = self.spanning_tree.discovered_by(current).src();
+            let arc = self.spanning_tree.discovered_by(current);
+            /* a vertex outside of the component of the root was not discovered by any arc */
+            if arc.dst() != current {
+                break;
+            }
+            let prev = arc.src();
             stack.push(prev);
             current = prev;
         }
@@ -108,7 +121,7 @@ impl<'a> TDBuilder<'a> {
             return;
         }
 
-        (stack.len() - 2..=0).for_each(|i| {
+        (0..stack.len() - 1).rev().for_each(|i| {
             let this_v = stack[i];
             let prev_v = stack[i + 1];
             self.on_tree_path[this_v] = [vec![prev_v], self.on_tree_path[prev_v].clone()].concat();
@@ -249,12 +262,10 @@ impl<'a> TreeDecomposable for SubTDBuilder<'a> {
     }
 
     fn add_vertex(&mut self, v: VertexId, to: BagId) {
-        if let Some(root) = self.donut.fake_root() {
-            if root == v {
-                return;
-            }
-        }
         let mapped_v = self.vertex_mapping(v);
+        if self.donut.fake_root() == Some(mapped_v) {
+            return;
+        }
         log_if_enabled!(SUB_TD_LOG, "adding local v{v} g{mapped_v} to bag{to}");
         self.bags[to].insert(mapped_v);
     }
@@ -299,21 +310,16 @@ impl<'a> SubTDBuilder<'a> {
         if self.donut.sub.num_vertices() == 0 {
             return;
         }
-        let global_root = 0;
+        /* the fake root replaces the root of the spanning tree, which is in the innermost donut */
+        let root = self.donut.get_local_index(self.spanning_tree.root());
         for v in 0..self.donut.sub.num_vertices() {
-            if v == match self.donut.fake_root() {
-                Some(root) => root,
-                None => global_root,
-            } {
+            if Some(v) == root {
                 self.tree_path_calculated[v] = true;
                 log_if_enabled!(SUB_TD_LOG, "fake root treepath calculated");
             } else if self.spanning_tree.vertex_level()[*self.donut.get_original_vertex(v).unwrap()]
                 == self.min_level
             {
-                match self.donut.fake_root() {
-                    Some(root) => self.on_tree_path[v].push(root),
-                    None => self.on_tree_path[v].push(global_root),
-                }
+                self.on_tree_path[v].extend(root);
                 self.tree_path_calculated[v] = true;
                 log_if_enabled!(
                     SUB_TD_LOG,
@@ -354,7 +360,7 @@ impl<'a> SubTDBuilder<'a> {
             return;
         }
 
-        (stack.len() - 2..=0).for_each(|i| {
+        (0..stack.len() - 1).rev().for_each(|i| {
             let this_v = stack[i];
             let prev_v = stack[i + 1];
             self.on_tree_path[this_v] = [vec![prev_v], self.on_tree_path[prev_v].clone()].concat();
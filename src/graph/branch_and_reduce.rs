@@ -0,0 +1,307 @@
+/* Exact maximum (weight) independent sets by branch and reduce:
+ * - The reductions of the input graph are applied exhaustively to every subproblem.
+ * - The remaining graph is split by taking or discarding a vertex of maximum degree.
+ * - Subproblems whose upper bound cannot beat the best set found so far are pruned.
+ */
+use super::dcel::vertex::VertexId;
+use super::heuristics::{find_greedy_mis, improve_mis};
+use super::mis_finder::Weight;
+use super::quick_graph::QuickGraph;
+use super::reducible::Reducible;
+use super::reductions::isolated_clique_reduction::{
+    do_isolated_clique_reductions, transfer_isolated_clique, IsolatedClique,
+};
+use super::reductions::nodal_fold_reduction::{
+    do_nodal_fold_reductions, transfer_nodal_fold_reductions, NodalFold,
+};
+use super::reductions::twin_reduction::{
+    do_twin_reductions, transfer_twin_reductions, TwinReduction,
+};
+
+/* the reductions of one round, the rounds are transferred in reverse order */
+enum AppliedReductions {
+    IsolatedCliques(Vec<IsolatedClique>),
+    NodalFolds(Vec<NodalFold>),
+    Twins(Vec<TwinReduction>),
+}
+
+impl AppliedReductions {
+    /* the number of vertices that the transfer adds to the independent set */
+    fn added_vertices(&self) -> usize {
+        match self {
+            AppliedReductions::IsolatedCliques(cliques) => cliques.len(),
+            AppliedReductions::NodalFolds(folds) => folds.len(),
+            AppliedReductions::Twins(twins) => 2 * twins.len(),
+        }
+    }
+
+    fn transfer(&mut self, independence_set: &mut Vec<VertexId>) {
+        match self {
+            AppliedReductions::IsolatedCliques(cliques) => {
+                transfer_isolated_clique(independence_set, cliques)
+            }
+            AppliedReductions::NodalFolds(folds) => {
+                transfer_nodal_fold_reductions(independence_set, folds)
+            }
+            AppliedReductions::Twins(twins) => transfer_twin_reductions(independence_set, twins),
+        }
+    }
+}
+
+/* applies the reductions until none of them is applicable */
+fn reduce(graph: &mut QuickGraph) -> Vec<AppliedReductions> {
+    let mut applied = vec![];
+    loop {
+        let rounds = [
+            AppliedReductions::IsolatedCliques(do_isolated_clique_reductions(graph)),
+            AppliedReductions::NodalFolds(do_nodal_fold_reductions(graph)),
+            AppliedReductions::Twins(do_twin_reductions(graph)),
+        ];
+        let before = applied.len();
+        applied.extend(
+            rounds
+                .into_iter()
+                .filter(|round| round.added_vertices() > 0),
+        );
+        if applied.len() == before {
+            return applied;
+        }
+    }
+}
+
+/// Returns an upper bound for the weight of an independent set of the graph.
+/// The vertices are greedily covered by cliques, and an independent set contains at most one
+/// vertex of every clique. So the bound is the sum of the largest weight in every clique.
+pub fn clique_cover_bound(graph: &QuickGraph, weights: &[Weight]) -> Weight {
+    clique_cover(graph, weights)
+        .into_iter()
+        .map(|v| weights[v])
+        .sum()
+}
+
+/// Greedily covers the vertices of the graph by cliques and returns the heaviest vertex of every
+/// clique, see [clique_cover_bound]. Every vertex joins the first clique that it is completely
+/// adjacent to, and only the cliques of its neighbors are checked, so this takes linear time
+/// after the vertices are sorted.
+pub fn clique_cover(graph: &QuickGraph, weights: &[Weight]) -> Vec<VertexId> {
+    let vertex_count = graph.adjacency.len();
+    let mut vertices: Vec<VertexId> = (0..vertex_count)
+        .filter(|&v| graph.adjacency[v].is_some())
+        .collect();
+    /* the first vertex of every clique is its heaviest one */
+    vertices.sort_by(|&u, &v| weights[v].cmp(&weights[u]).then(u.cmp(&v)));
+
+    let mut heaviest: Vec<VertexId> = vec![];
+    let mut clique_size: Vec<usize> = vec![];
+    let mut clique_of: Vec<Option<usize>> = vec![None; vertex_count];
+    /* the number of distinct neighbors of the current vertex in every clique */
+    let mut shared = vec![0; vertex_count];
+    let mut counted = vec![usize::MAX; vertex_count];
+    for v in vertices {
+        let neighbors = graph.adjacency[v].as_deref().unwrap_or_default();
+        for &u in neighbors {
+            if let (Some(clique), true) = (clique_of[u], counted[u] != v) {
+                counted[u] = v;
+                shared[clique] += 1;
+            }
+        }
+        let clique = neighbors
+            .iter()
+            .filter_map(|&u| clique_of[u])
+            .filter(|&clique| shared[clique] == clique_size[clique])
+            .min();
+        neighbors
+            .iter()
+            .filter_map(|&u| clique_of[u])
+            .for_each(|clique| shared[clique] = 0);
+
+        match clique {
+            Some(clique) => {
+                clique_of[v] = Some(clique);
+                clique_size[clique] += 1;
+            }
+            None => {
+                clique_of[v] = Some(heaviest.len());
+                heaviest.push(v);
+                clique_size.push(1);
+            }
+        }
+    }
+    heaviest
+}
+
+struct Search<'a> {
+    weights: &'a [Weight],
+    /* the reductions only preserve maximum independent sets if all weights are the same */
+    reduce: bool,
+    best_weight: Weight,
+}
+
+impl Search<'_> {
+    /* returns the best set of the graph if its weight together with `fixed` beats the best one */
+    fn search(&mut self, mut graph: QuickGraph, fixed: Weight) -> Option<Vec<VertexId>> {
+        let mut applied = match self.reduce {
+            true => reduce(&mut graph),
+            false => vec![],
+        };
+        let unit = self.weights.first().copied().unwrap_or(0);
+        let fixed = fixed
+            + applied
+                .iter()
+                .map(|round| round.added_vertices() * unit)
+                .sum::<Weight>();
+        if fixed + clique_cover_bound(&graph, self.weights) <= self.best_weight {
+            return None;
+        }
+
+        let branch_vertex = (0..graph.adjacency.len())
+            .filter_map(|v| {
+                graph.adjacency[v]
+                    .as_ref()
+                    .map(|neighbors| (v, neighbors.len()))
+            })
+            .max_by(|(u, a), (v, b)| a.cmp(b).then(v.cmp(u)))
+            .map(|(v, _)| v);
+
+        let mut set = match branch_vertex {
+            None => {
+                /* the bound is 0 for an empty graph, so `fixed` beats the best set */
+                self.best_weight = fixed;
+                vec![]
+            }
+            Some(v) => {
+                /* take v and discard its neighbors */
+                let mut taken = graph.clone();
+                for neighbor in graph.adjacency[v].clone().unwrap() {
+                    taken.remove_vertex(neighbor);
+                }
+                taken.remove_vertex(v);
+                let with_v = self.search(taken, fixed + self.weights[v]).map(|mut set| {
+                    set.push(v);
+                    set
+                });
+
+                /* discard v, a later improvement is better than the set with v */
+                graph.remove_vertex(v);
+                self.search(graph, fixed).or(with_v)?
+            }
+        };
+
+        for round in applied.iter_mut().rev() {
+            round.transfer(&mut set);
+        }
+        Some(set)
+    }
+}
+
+/// Finds a maximum weight independent set of the graph and returns it together with its weight.
+/// The greedy set of [find_greedy_mis] is the first lower bound, subproblems are pruned with
+/// [clique_cover_bound]. If all vertices have the same weight, the twin, isolated clique and
+/// nodal fold reductions are applied before every branch.
+/// In contrast to [crate::graph::mis_finder::find_mis_exhaustive], the running time depends on
+/// the structure of the graph rather than only on its size, so medium graphs can be solved.
+pub fn find_mis_branch_and_reduce(
+    graph: &QuickGraph,
+    weights: &[Weight],
+) -> (Vec<VertexId>, Weight) {
+    let greedy = improve_mis(graph, weights, &find_greedy_mis(graph, weights));
+    let greedy_weight = greedy.iter().map(|&v| weights[v]).sum();
+
+    let mut search = Search {
+        weights,
+        reduce: weights.iter().all(|&weight| weight == weights[0]),
+        best_weight: greedy_weight,
+    };
+    match search.search(graph.clone(), 0) {
+        Some(set) => (set, search.best_weight),
+        None => (greedy, greedy_weight),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{clique_cover_bound, find_mis_branch_and_reduce};
+    use crate::graph::mis_finder::{find_connected_vertices, find_weighted_mis_exhaustive};
+    use crate::graph::quick_graph::QuickGraph;
+    use crate::{read_graph_file_into_dcel_builder, solve, Scheme, SolverConfig};
+    use std::collections::HashSet;
+
+    #[test]
+    fn branch_and_reduce() {
+        for file in ["tree", "tri", "simple", "bsp1", "bsp3"] {
+            let mut builder =
+                read_graph_file_into_dcel_builder(&format!("data/{file}.graph")).unwrap();
+            let graph = QuickGraph::from(&builder);
+            let adjacency = builder.build().adjacency_matrix();
+            for weights in [
+                vec![1; adjacency.len()],
+                (0..adjacency.len()).map(|v| v * 7 % 5 + 1).collect(),
+            ] {
+                let (set, weight) = find_mis_branch_and_reduce(&graph, &weights);
+                let unique: HashSet<usize> = set.iter().copied().collect();
+                assert_eq!(unique.len(), set.len(), "{file}");
+                assert!(find_connected_vertices(&unique, &adjacency).is_empty());
+                assert_eq!(set.iter().map(|&v| weights[v]).sum::<usize>(), weight);
+                assert!(weight <= clique_cover_bound(&graph, &weights));
+
+                if adjacency.len() <= 16 {
+                    let (_, optimum) = find_weighted_mis_exhaustive(&adjacency, &weights).unwrap();
+                    assert_eq!(weight, optimum, "{file}");
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn large_graphs() {
+        /* these graphs are too large to enumerate all subsets, the dynamic program of the whole
+         * graph finds the optimum */
+        for file in ["bsp1", "bsp2", "bsp3"] {
+            let builder = read_graph_file_into_dcel_builder(&format!("data/{file}.graph")).unwrap();
+            let graph = QuickGraph::from(&builder);
+            let n = builder.num_vertices();
+            for weights in [vec![1; n], (0..n).map(|v| v % 4 + 1).collect()] {
+                let (_, weight) = find_mis_branch_and_reduce(&graph, &weights);
+                let config = SolverConfig::new(Scheme::AllWithTD).with_weights(weights.clone());
+                assert_eq!(weight, solve(&builder, config).unwrap().weight, "{file}");
+            }
+        }
+    }
+
+    #[test]
+    fn clique_cover_of_grid() {
+        /* a 300 x 300 grid has 90000 vertices, a quadratic cover would not finish */
+        let side = 300;
+        let mut graph = QuickGraph::new(side * side);
+        for v in 0..side * side {
+            let neighbors = graph.adjacency[v].as_mut().unwrap();
+            if v % side > 0 {
+                neighbors.push(v - 1);
+            }
+            if v % side + 1 < side {
+                neighbors.push(v + 1);
+            }
+            if v >= side {
+                neighbors.push(v - side);
+            }
+            if v + side < side * side {
+                neighbors.push(v + side);
+            }
+        }
+        let weights = vec![1; side * side];
+
+        let bound = clique_cover_bound(&graph, &weights);
+        /* the cliques of a grid are its edges, and a perfect matching covers it */
+        assert_eq!(bound, side * side / 2);
+
+        /* a triangle is a single clique, a path of three vertices needs two, the lighter end
+         * has a clique of its own */
+        let mut triangle = QuickGraph::new(3);
+        triangle.adjacency = vec![Some(vec![1, 2]), Some(vec![0, 2]), Some(vec![0, 1])];
+        assert_eq!(clique_cover_bound(&triangle, &[1, 2, 3]), 3);
+        triangle.adjacency[0] = Some(vec![1]);
+        triangle.adjacency[2] = Some(vec![1]);
+        triangle.adjacency[1] = Some(vec![0, 2]);
+        assert_eq!(clique_cover_bound(&triangle, &[1, 2, 3]), 4);
+    }
+}
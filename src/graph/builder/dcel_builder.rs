@@ -68,6 +68,14 @@ impl DcelBuilder {
         }
     }
 
+    /// Returns a builder with `count` vertices without arcs, so that isolated vertices are kept.
+    pub fn with_vertex_count(count: usize) -> Self {
+        DcelBuilder {
+            vertices: vec![Vertex::new(); count],
+            ..DcelBuilder::new()
+        }
+    }
+
     pub fn vertex_count(&self) -> usize {
         self.vertices.len()
     }
@@ -101,6 +109,27 @@ impl DcelBuilder {
         }
     }
 
+    /// Returns the embedding of the subgraph that is induced by `vertices`, whose vertex `i` is
+    /// `vertices[i]`. The arcs keep their order around each vertex, so the subgraph of a
+    /// connected component keeps its faces.
+    pub fn subgraph(&self, vertices: &[VertexId]) -> DcelBuilder {
+        let mut index = vec![None; self.vertices.len()];
+        vertices
+            .iter()
+            .enumerate()
+            .for_each(|(i, &v)| index[v] = Some(i));
+
+        let mut subgraph = DcelBuilder::with_vertex_count(vertices.len());
+        for (src, &v) in vertices.iter().enumerate() {
+            for &arc in &self.vertices[v].arcs {
+                if let Some(dst) = index[self.arcs[arc].dst] {
+                    subgraph.push_arc(src, dst);
+                }
+            }
+        }
+        subgraph
+    }
+
     pub fn build(&mut self) -> Dcel {
         self.set_dest_ports();
         self.build_faces();
@@ -261,6 +290,10 @@ impl DcelBuilder {
         self.vertices.len()
     }
 
+    pub fn num_arcs(&self) -> usize {
+        self.arcs.len()
+    }
+
     pub fn arc(&self, id: ArcId) -> &Arc {
         &self.arcs[id]
     }
@@ -3,3 +3,4 @@ use super::dcel;
 pub mod dcel_builder;
 
 pub mod types;
+pub mod validation;
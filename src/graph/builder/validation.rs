@@ -0,0 +1,283 @@
+use std::collections::HashSet;
+
+use super::dcel_builder::DcelBuilder;
+use crate::graph::dcel::arc::ArcId;
+use crate::graph::dcel::vertex::VertexId;
+
+/// Represents a single problem of an embedding that was found by [validate_embedding].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum EmbeddingIssue {
+    /// There is no arc `dst -> src` for the arc `src -> dst`.
+    MissingTwin {
+        arc: ArcId,
+        src: VertexId,
+        dst: VertexId,
+    },
+    /// The arc `src -> dst` appears more than once.
+    DuplicateArc { src: VertexId, dst: VertexId },
+    /// The vertex has an arc to itself.
+    SelfLoop { vertex: VertexId },
+    /// The vertex has no arcs.
+    IsolatedVertex { vertex: VertexId },
+    /// The faces of the rotation system of the component that contains `vertex` violate Euler's
+    /// formula `V - E + F = 2`, so the rotation system does not describe a planar embedding.
+    NotPlanar {
+        vertex: VertexId,
+        vertices: usize,
+        edges: usize,
+        faces: usize,
+    },
+}
+
+impl EmbeddingIssue {
+    /// Returns true if the embedding cannot be solved because of this issue.
+    /// Isolated vertices are no problem for the DCEL, so they are only reported.
+    pub fn is_fatal(&self) -> bool {
+        !matches!(self, EmbeddingIssue::IsolatedVertex { .. })
+    }
+}
+
+impl std::fmt::Display for EmbeddingIssue {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            EmbeddingIssue::MissingTwin { arc, src, dst } => {
+                write!(f, "arc {arc} ({src} -> {dst}) has no twin {dst} -> {src}")
+            }
+            EmbeddingIssue::DuplicateArc { src, dst } => {
+                write!(f, "arc {src} -> {dst} appears more than once")
+            }
+            EmbeddingIssue::SelfLoop { vertex } => write!(f, "vertex {vertex} has a self-loop"),
+            EmbeddingIssue::IsolatedVertex { vertex } => write!(f, "vertex {vertex} is isolated"),
+            EmbeddingIssue::NotPlanar {
+                vertex,
+                vertices,
+                edges,
+                faces,
+            } => write!(
+                f,
+                "the component of vertex {vertex} is not embedded planar: \
+                 V - E + F = {vertices} - {edges} + {faces} != 2"
+            ),
+        }
+    }
+}
+
+/// Contains all issues of an embedding that is not valid.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct InvalidEmbedding {
+    pub issues: Vec<EmbeddingIssue>,
+}
+
+impl InvalidEmbedding {
+    pub fn is_fatal(&self) -> bool {
+        self.issues.iter().any(EmbeddingIssue::is_fatal)
+    }
+}
+
+impl std::error::Error for InvalidEmbedding {}
+
+impl std::fmt::Display for InvalidEmbedding {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "the embedding has {} issue(s):", self.issues.len())?;
+        for issue in &self.issues {
+            write!(f, "\n  {issue}")?;
+        }
+        Ok(())
+    }
+}
+
+/// Checks that the builder describes a planar embedding that can be built into a [Dcel].
+///
+/// Every arc needs exactly one twin, there must be no self-loops and no isolated vertices.
+/// If the arcs are fine, the faces of the rotation system are counted and Euler's formula
+/// `V - E + F = 2` is checked for each connected component. Since every component has its own
+/// outer face, this is the same as `V - E + F = 1 + C` for the whole graph with one outer face.
+///
+/// [Dcel]: crate::graph::Dcel
+pub fn validate_embedding(builder: &DcelBuilder) -> Result<(), InvalidEmbedding> {
+    let mut issues = vec![];
+
+    let mut seen_arcs = HashSet::new();
+    let mut reported_duplicates = HashSet::new();
+    for vertex in 0..builder.num_vertices() {
+        let arcs = builder.arcs(vertex);
+        if arcs.is_empty() {
+            issues.push(EmbeddingIssue::IsolatedVertex { vertex });
+        }
+        for arc_id in arcs {
+            let arc = builder.arc(arc_id);
+            if arc.src == arc.dst {
+                issues.push(EmbeddingIssue::SelfLoop { vertex: arc.src });
+            }
+            if !seen_arcs.insert((arc.src, arc.dst))
+                && reported_duplicates.insert((arc.src, arc.dst))
+            {
+                issues.push(EmbeddingIssue::DuplicateArc {
+                    src: arc.src,
+                    dst: arc.dst,
+                });
+            }
+            if arc.twin.is_none() {
+                issues.push(EmbeddingIssue::MissingTwin {
+                    arc: arc_id,
+                    src: arc.src,
+                    dst: arc.dst,
+                });
+            }
+        }
+    }
+
+    /* faces can only be traced if every arc has exactly one twin */
+    if !issues.iter().any(EmbeddingIssue::is_fatal) {
+        check_euler_formula(builder, &mut issues);
+    }
+
+    match issues.is_empty() {
+        true => Ok(()),
+        false => Err(InvalidEmbedding { issues }),
+    }
+}
+
+/* counts vertices, edges and faces of each component and checks V - E + F = 2 */
+fn check_euler_formula(builder: &DcelBuilder, issues: &mut Vec<EmbeddingIssue>) {
+    let num_arcs = builder.num_arcs();
+
+    /* successor of each arc in the rotation of its source vertex */
+    let mut rotation_next = vec![0; num_arcs];
+    for vertex in 0..builder.num_vertices() {
+        let rotation = builder.arcs(vertex);
+        for (position, &arc_id) in rotation.iter().enumerate() {
+            rotation_next[arc_id] = rotation[(position + 1) % rotation.len()];
+        }
+    }
+
+    /* find connected components */
+    let mut component: Vec<Option<usize>> = vec![None; builder.num_vertices()];
+    let mut component_roots = vec![];
+    for root in 0..builder.num_vertices() {
+        if component[root].is_some() || builder.arcs(root).is_empty() {
+            continue;
+        }
+        let current = component_roots.len();
+        component_roots.push(root);
+        component[root] = Some(current);
+        let mut stack = vec![root];
+        while let Some(vertex) = stack.pop() {
+            for arc_id in builder.arcs(vertex) {
+                let dst = builder.arc(arc_id).dst;
+                if component[dst].is_none() {
+                    component[dst] = Some(current);
+                    stack.push(dst);
+                }
+            }
+        }
+    }
+
+    let mut vertices = vec![0; component_roots.len()];
+    let mut arcs = vec![0; component_roots.len()];
+    let mut faces = vec![0; component_roots.len()];
+    for (vertex, c) in component.iter().enumerate() {
+        if let Some(c) = *c {
+            vertices[c] += 1;
+            arcs[c] += builder.arcs(vertex).len();
+        }
+    }
+
+    /* trace the faces the same way DcelBuilder::build does */
+    let mut visited = vec![false; num_arcs];
+    for start in 0..num_arcs {
+        if visited[start] {
+            continue;
+        }
+        faces[component[builder.arc(start).src].unwrap()] += 1;
+        let mut current = start;
+        while !visited[current] {
+            visited[current] = true;
+            current = rotation_next[builder.arc(current).twin.unwrap()];
+        }
+    }
+
+    for (c, &vertex) in component_roots.iter().enumerate() {
+        let edges = arcs[c] / 2;
+        if vertices[c] + faces[c] != 2 + edges {
+            issues.push(EmbeddingIssue::NotPlanar {
+                vertex,
+                vertices: vertices[c],
+                edges,
+                faces: faces[c],
+            });
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{validate_embedding, EmbeddingIssue};
+    use crate::graph::DcelBuilder;
+
+    fn builder(arcs: &[(usize, usize)]) -> DcelBuilder {
+        let mut builder = DcelBuilder::new();
+        for &(u, v) in arcs {
+            builder.push_arc(u, v);
+        }
+        builder
+    }
+
+    #[test]
+    fn valid() {
+        let triangle = builder(&[(0, 1), (0, 2), (1, 2), (1, 0), (2, 0), (2, 1)]);
+        assert_eq!(validate_embedding(&triangle), Ok(()));
+
+        let two_edges = builder(&[(0, 1), (1, 0), (2, 3), (3, 2)]);
+        assert_eq!(validate_embedding(&two_edges), Ok(()));
+    }
+
+    #[test]
+    fn arc_issues() {
+        let graph = builder(&[(0, 1), (1, 0), (1, 2), (0, 1), (3, 3)]);
+        let issues = validate_embedding(&graph).unwrap_err().issues;
+        assert!(issues.contains(&EmbeddingIssue::MissingTwin {
+            arc: 2,
+            src: 1,
+            dst: 2
+        }));
+        assert!(issues.contains(&EmbeddingIssue::DuplicateArc { src: 0, dst: 1 }));
+        assert!(issues.contains(&EmbeddingIssue::SelfLoop { vertex: 3 }));
+        assert!(issues.contains(&EmbeddingIssue::IsolatedVertex { vertex: 2 }));
+
+        let isolated = builder(&[(0, 1), (1, 0), (3, 1), (1, 3)]);
+        let invalid = validate_embedding(&isolated).unwrap_err();
+        assert_eq!(
+            invalid.issues,
+            vec![EmbeddingIssue::IsolatedVertex { vertex: 2 }]
+        );
+        assert!(!invalid.is_fatal());
+    }
+
+    #[test]
+    fn not_planar() {
+        /* K4 where only the rotation of vertex 0 is counterclockwise, which embeds it on a torus */
+        let k4 = builder(&[
+            (0, 1),
+            (0, 2),
+            (0, 3),
+            (1, 0),
+            (1, 2),
+            (1, 3),
+            (2, 0),
+            (2, 3),
+            (2, 1),
+            (3, 0),
+            (3, 1),
+            (3, 2),
+        ]);
+        match validate_embedding(&k4).unwrap_err().issues.as_slice() {
+            [EmbeddingIssue::NotPlanar {
+                vertices: 4,
+                edges: 6,
+                ..
+            }] => {}
+            issues => panic!("Unexpected issues: {issues:?}"),
+        }
+    }
+}
@@ -3,7 +3,7 @@ pub mod face;
 pub mod spanning_tree;
 pub mod vertex;
 
-use std::{collections::HashSet, error::Error};
+use std::{collections::HashSet, error::Error, ops::Range};
 
 use self::face::FaceIterator;
 use super::{
@@ -130,6 +130,23 @@ impl Dcel {
         tree
     }
 
+    /// Returns the vertices of each connected component, ordered by their smallest vertex.
+    pub fn connected_components(&self) -> Vec<Vec<VertexId>> {
+        let mut visited = vec![false; self.num_vertices()];
+        let mut components = vec![];
+        for start in 0..self.num_vertices() {
+            if visited[start] {
+                continue;
+            }
+            let mut component: Vec<VertexId> =
+                BfsIter::new(self, start).map(|it| it.vertex).collect();
+            component.iter().for_each(|&v| visited[v] = true);
+            component.sort_unstable();
+            components.push(component);
+        }
+        components
+    }
+
     pub fn has_arc(&self, u: VertexId, v: VertexId) -> bool {
         self.arc_set
             .contains(&[u.to_string(), v.to_string()].join(" "))
@@ -597,75 +614,119 @@ impl Dcel {
         i: usize,
         spanning_tree: &SpanningTree,
     ) -> Result<Vec<SubDcel>, Box<dyn Error>> {
-        let mut result = vec![];
-        let mut clone = self.clone();
-        let root = spanning_tree.root();
+        self.donuts(spanning_tree.donut_levels(k, i), spanning_tree)
+    }
 
-        let mut last_level = 0;
+    /// The whole graph as a single donut of all levels, which is solved if `k` is not below the
+    /// levels of the spanning tree.
+    pub fn whole_graph_donut(
+        &self,
+        spanning_tree: &SpanningTree,
+    ) -> Result<SubDcel, Box<dyn Error>> {
+        let levels = 0..spanning_tree.max_level() + 1;
+        Ok(self.donuts(vec![levels], spanning_tree)?.remove(0))
+    }
 
-        for n in 1..(spanning_tree.max_level() + 1) {
-            if n % (k + 1) == i {
-                log_if_enabled!(LOG, "Find Donuts: level {last_level} to {n}");
-                /* Current donut is from last_level -> n */
-                let mut donut = clone.collect_donut(last_level, n, root, &spanning_tree)?;
-                // todo:
-                log_if_enabled!(
-                    LOG,
-                    "arc count: {}, face count: {}",
-                    donut.sub.num_arcs(),
-                    donut.sub.num_faces()
-                );
-                for f in 0..donut.sub.num_faces() {
-                    log_if_enabled!(LOG, "face{f}:");
-                    for a in donut.sub.walk_face(f) {
-                        log_if_enabled!(
-                            LOG,
-                            " v{},",
-                            self.arc(*donut.get_original_arc(a).unwrap()).src()
-                        )
-                    }
-                    log_if_enabled!(LOG, "");
-                }
+    /* the donuts of consecutive level ranges, the levels before a donut are contracted into the
+     * root of the spanning tree, which is the fake root of the donut */
+    fn donuts(
+        &self,
+        donut_levels: Vec<Range<usize>>,
+        spanning_tree: &SpanningTree,
+    ) -> Result<Vec<SubDcel>, Box<dyn Error>> {
+        if !spanning_tree.is_spanning() {
+            return Err("The graph is not connected, its components need their own donuts".into());
+        }
+        Ok(donut_levels
+            .into_iter()
+            .map(|levels| {
+                log_if_enabled!(LOG, "Find Donuts: level {} to {}", levels.start, levels.end);
+                let mut donut = self.contracted_donut(levels, spanning_tree);
                 donut.triangulate();
-                log_if_enabled!(
-                    LOG,
-                    "after triangulation arc count: {}, face count: {}",
-                    donut.sub.num_arcs(),
-                    donut.sub.num_faces()
-                );
-                for f in 0..donut.sub.num_faces() {
-                    log_if_enabled!(LOG, "face{f}:");
-                    for a in donut.sub.walk_face(f) {
-                        log_if_enabled!(LOG, " v{}", donut.vertex_mapping[donut.sub.arc(a).src()]);
-                    }
-                    log_if_enabled!(LOG, "");
+                donut
+            })
+            .collect())
+    }
+
+    /* The donut of the vertices on `levels`. The levels above it are a subtree of the spanning
+     * tree, and contracting it keeps the embedding planar: Walking around the subtree meets the
+     * arcs that leave it in the order of the contracted vertex. Only the first arc to each vertex
+     * of the donut is kept, so the fake root has no parallel arcs. */
+    fn contracted_donut(&self, levels: Range<usize>, spanning_tree: &SpanningTree) -> SubDcel {
+        let level = spanning_tree.vertex_level();
+        let mut vertex_mapping: Vec<VertexId> = (0..self.num_vertices())
+            .filter(|&v| levels.contains(&level[v]))
+            .collect();
+        let mut local = vec![0; self.num_vertices()];
+        vertex_mapping
+            .iter()
+            .enumerate()
+            .for_each(|(i, &v)| local[v] = i);
+
+        let fake_root = (levels.start > 0).then_some(spanning_tree.root());
+        let fake = vertex_mapping.len();
+        let mut root_arcs = vec![];
+        let mut kept = vec![false; self.num_arcs()];
+        if let Some(root) = fake_root {
+            vertex_mapping.push(root);
+            let mut reached = vec![false; self.num_vertices()];
+            /* each vertex of the walk with the position of its next arc and its remaining arcs,
+             * a vertex is left through the twin of the arc it was entered by */
+            let mut stack = vec![(root, 0, self.vertex(root).arcs().len())];
+            while let Some((u, next, remaining)) = stack.last_mut() {
+                if *remaining == 0 {
+                    stack.pop();
+                    continue;
                 }
-                result.push(donut);
-
-                // after creating the donut we merge it into the root of the tree to create a fake
-                // root for the following donut
-                for i in last_level..=n {
-                    spanning_tree
-                        .on_level(i)
-                        .iter()
-                        .for_each(|v| clone.merge_vertices(root, *v))
+                let arcs = self.vertex(*u).arcs();
+                let arc = arcs[*next % arcs.len()];
+                (*next, *remaining) = (*next + 1, *remaining - 1);
+                let (u, w) = (*u, self.arc(arc).dst());
+                if level[w] >= levels.start {
+                    if !reached[w] {
+                        reached[w] = true;
+                        kept[arc] = true;
+                        root_arcs.push(arc);
+                    }
+                } else if level[w] == level[u] + 1 && spanning_tree.discovered_by(w).src() == u {
+                    let twin = self.arc(arc).twin();
+                    let position = self.vertex(w).arcs().iter().position(|&a| a == twin);
+                    let degree = self.vertex(w).arcs().len();
+                    stack.push((w, position.unwrap() + 1, degree - 1));
                 }
-                last_level = n + 1;
             }
         }
 
-        if last_level < spanning_tree.max_level() + 1 {
-            let mut last_donut = clone.collect_donut(
-                last_level,
-                spanning_tree.max_level() + 1,
-                root,
-                &spanning_tree,
-            )?;
-            last_donut.triangulate();
-            result.push(last_donut);
+        let mut builder = DcelBuilder::with_vertex_count(vertex_mapping.len());
+        let mut arc_mapping = vec![];
+        for (src, &v) in vertex_mapping.iter().enumerate() {
+            if src == fake {
+                for &arc in &root_arcs {
+                    builder.push_arc(src, local[self.arc(arc).dst()]);
+                    arc_mapping.push(arc);
+                }
+                continue;
+            }
+            for &arc in self.vertex(v).arcs() {
+                let w = self.arc(arc).dst();
+                if levels.contains(&level[w]) {
+                    builder.push_arc(src, local[w]);
+                    arc_mapping.push(arc);
+                } else if level[w] < levels.start && kept[self.arc(arc).twin()] {
+                    builder.push_arc(src, fake);
+                    arc_mapping.push(arc);
+                }
+            }
         }
 
-        Ok(result)
+        SubDcel::new(
+            self.clone(),
+            builder.build(),
+            arc_mapping,
+            vertex_mapping,
+            fake_root,
+            Some(levels.start),
+        )
     }
 
     pub fn pre_triangulation_arc_count(&self) -> usize {
@@ -675,9 +736,9 @@ impl Dcel {
 
 #[cfg(test)]
 mod tests {
-    use crate::{log_if_enabled, read_graph_file_into_dcel_builder, write_web_file};
+    use crate::{log_if_enabled, read_graph_file_into_dcel_builder, write_web_file, MISResult};
 
-    use super::Dcel;
+    use super::{Dcel, LOG};
 
     #[test]
     fn adjacency_matrix() {
@@ -704,7 +765,7 @@ mod tests {
         // dcel.merge_vertices(0, 7);
         // dcel.merge_vertices(0, 6);
         let mut clone = dcel.clone();
-        write_web_file("data/test.js", &clone);
+        write_web_file(&web_file(), &clone, MISResult::default()).unwrap();
     }
 
     #[test]
@@ -717,7 +778,7 @@ mod tests {
         let mut clone = dcel.clone();
         let st = dcel.spanning_tree(0);
         show_relevant_stuff(&clone);
-        write_web_file("data/test.js", &clone);
+        write_web_file(&web_file(), &clone, MISResult::default()).unwrap();
     }
     #[test]
     fn merge_vertices_circ() {
@@ -729,7 +790,12 @@ mod tests {
         let mut clone = dcel.clone();
         let st = dcel.spanning_tree(0);
         show_relevant_stuff(&clone);
-        write_web_file("data/test.js", &clone);
+        write_web_file(&web_file(), &clone, MISResult::default()).unwrap();
+    }
+
+    /* the web file of the tests is not written into the repository */
+    fn web_file() -> String {
+        std::env::temp_dir().join("test.js").display().to_string()
     }
 
     fn show_relevant_stuff(g: &Dcel) {
@@ -762,6 +828,6 @@ mod tests {
         //         .iter()
         //         .for_each(|v| clone.merge_vertices(0, *v));
         // }
-        write_web_file("data/test.js", &clone);
+        write_web_file(&web_file(), &clone, MISResult::default()).unwrap();
     }
 }
@@ -1,3 +1,5 @@
+use std::ops::Range;
+
 use super::arc::Arc;
 use super::BfsIter;
 use super::{ArcId, Dcel, VertexId};
@@ -83,7 +85,80 @@ impl<'a> SpanningTree<'a> {
         self.max_level
     }
 
+    /// The levels of the donuts of offset `i`, each donut covers the levels `start..end` of its
+    /// range. The levels `n >= 1` with `n % (k + 1) == i` lie between the donuts and are left
+    /// out, the root is never left out.
+    pub fn donut_levels(&self, k: usize, i: usize) -> Vec<Range<usize>> {
+        let mut levels = vec![];
+        let mut last_level = 0;
+        for n in 1..=self.max_level {
+            if n % (k + 1) == i {
+                levels.push(last_level..n);
+                last_level = n + 1;
+            }
+        }
+        if last_level < self.max_level + 1 {
+            levels.push(last_level..self.max_level + 1);
+        }
+        levels
+    }
+
+    /// Whether the tree reaches every vertex of the graph. The breadth-first search only visits
+    /// the component of the root, the other vertices keep level 0 and are discovered by arc 0.
+    pub fn is_spanning(&self) -> bool {
+        self.arcs.len() / 2 + 1 == self.dcel.num_vertices()
+    }
+
     pub fn discovered_by(&self, v: VertexId) -> &Arc {
         self.dcel.arc(self.discovered_by[v])
     }
 }
+
+/// Returns the number of levels below `start` in a breadth-first search and a vertex on the last
+/// level.
+pub fn eccentricity(dcel: &Dcel, start: VertexId) -> (usize, VertexId) {
+    BfsIter::new(dcel, start).fold((0, start), |_, it| (it.level, it.vertex))
+}
+
+/// Returns the middle vertex of a long shortest path, which is found by two breadth-first
+/// searches: The path starts at a vertex that is farthest from `start` and ends at a vertex that
+/// is farthest from the first one. A spanning tree rooted at the middle vertex has about half as
+/// many levels as the path is long.
+pub fn pseudo_diameter_center(dcel: &Dcel, start: VertexId) -> VertexId {
+    let (_, first) = eccentricity(dcel, start);
+    let tree = dcel.spanning_tree(first);
+    let (length, mut center) = eccentricity(dcel, first);
+    for _ in 0..length / 2 {
+        center = tree.discovered_by(center).src();
+    }
+    center
+}
+
+/// Returns the vertex with the fewest levels below it among the [pseudo_diameter_center] and
+/// about `samples` evenly spaced vertices of the component of `start`.
+pub fn min_eccentricity_vertex(dcel: &Dcel, start: VertexId, samples: usize) -> VertexId {
+    let component: Vec<VertexId> = BfsIter::new(dcel, start).map(|it| it.vertex).collect();
+    let step = (component.len() / samples.max(1)).max(1);
+    std::iter::once(pseudo_diameter_center(dcel, start))
+        .chain(component.into_iter().step_by(step))
+        .min_by_key(|&v| eccentricity(dcel, v).0)
+        .unwrap()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{min_eccentricity_vertex, pseudo_diameter_center};
+    use crate::read_graph_file_into_dcel_builder;
+
+    #[test]
+    fn root_selection() {
+        let graph = read_graph_file_into_dcel_builder("data/bsp2.graph")
+            .unwrap()
+            .build();
+        let levels = |root| graph.spanning_tree(root).max_level();
+        let center = pseudo_diameter_center(&graph, 0);
+        let eccentricity = min_eccentricity_vertex(&graph, 0, 16);
+        assert!(levels(center) < levels(0));
+        assert!(levels(eccentricity) <= levels(center));
+    }
+}
@@ -1,4 +1,4 @@
-use crate::MISResult;
+use crate::{GraphStats, MISResult, VertexCover};
 
 use super::approximated_td::ApproximatedTD;
 use super::approximated_td::TDBuilder;
@@ -7,8 +7,10 @@ use super::dcel::vertex::VertexId;
 use super::sub_dcel::SubDcel;
 use super::Dcel;
 use std::collections::HashSet;
+use std::error::Error;
 use std::fs::File;
 use std::io::prelude::*;
+use std::path::Path;
 
 #[derive(Clone)]
 struct JsValue<'a> {
@@ -117,9 +119,30 @@ impl WebFileWriter for usize {
     }
 }
 
+impl WebFileWriter for f64 {
+    fn write_to_file(&self, file: &mut File, _id: usize, _level: u32) -> std::io::Result<()> {
+        write!(*file, "{}", self)
+    }
+}
+
 impl WebFileWriter for &str {
     fn write_to_file(&self, file: &mut File, _id: usize, _level: u32) -> std::io::Result<()> {
-        write!(*file, "\"{}\"", self)
+        write!(*file, "\"")?;
+        for c in self.chars() {
+            match c {
+                '"' => write!(*file, "\\\"")?,
+                '\\' => write!(*file, "\\\\")?,
+                c if c.is_control() => write!(*file, "\\u{:04x}", c as u32)?,
+                c => write!(*file, "{c}")?,
+            }
+        }
+        write!(*file, "\"")
+    }
+}
+
+impl WebFileWriter for String {
+    fn write_to_file(&self, file: &mut File, id: usize, level: u32) -> std::io::Result<()> {
+        self.as_str().write_to_file(file, id, level)
     }
 }
 
@@ -312,6 +335,7 @@ impl WebFileWriter for MISResult {
                     JsValue::new("mis", &JsArray::new(&self.result)),
                     JsValue::new("k", &self.k),
                     JsValue::new("i", &self.i),
+                    JsValue::new("root", &self.root),
                 ],
             },
         }
@@ -319,19 +343,26 @@ impl WebFileWriter for MISResult {
     }
 }
 
-impl WebFileWriter for Dcel {
+/* the graph with the spanning tree and its tree decomposition from a root */
+struct JsDcel<'a> {
+    dcel: &'a Dcel,
+    root: VertexId,
+}
+
+impl<'a> WebFileWriter for JsDcel<'a> {
     fn write_to_file(&self, file: &mut File, id: usize, level: u32) -> std::io::Result<()> {
-        let v = self
+        let dcel = self.dcel;
+        let v = dcel
             .vertices()
             .iter()
             .enumerate()
             .map(|(i, _v)| JsVertex::new(i))
             .collect();
-        let st = self.spanning_tree(0);
+        let st = dcel.spanning_tree(self.root);
         let mut b = TDBuilder::new(&st);
         let approx_td = ApproximatedTD::from(&mut b);
         let s = st.arcs();
-        let a = self
+        let a = dcel
             .arcs()
             .iter()
             .enumerate()
@@ -340,18 +371,18 @@ impl WebFileWriter for Dcel {
                     i,
                     a.src(),
                     a.dst(),
-                    i >= self.pre_triangulation_arc_count(),
-                    self.invalid_arcs[i],
+                    i >= dcel.pre_triangulation_arc_count(),
+                    dcel.invalid_arcs[i],
                 )
             })
             .collect();
-        let faces = self.faces();
+        let faces = dcel.faces();
         println!("faces: {:?}", faces.len());
         let arcs_per_faces: Vec<Vec<usize>> =
-            faces.iter().map(|face| face.walk_face(self)).collect();
+            faces.iter().map(|face| face.walk_face(dcel)).collect();
         let verts_per_face: Vec<Vec<usize>> = arcs_per_faces
             .iter()
-            .map(|arcs| arcs.iter().map(|arc| self.arc(*arc).src()).collect())
+            .map(|arcs| arcs.iter().map(|arc| dcel.arc(*arc).src()).collect())
             .collect();
         let mut js_faces = vec![];
         for (i, _) in faces.iter().enumerate() {
@@ -362,7 +393,7 @@ impl WebFileWriter for Dcel {
             ));
         }
 
-        let rings = &self.find_rings().unwrap();
+        let rings = &dcel.find_rings().unwrap();
 
         JsObject {
             item: &JsValues {
@@ -380,6 +411,125 @@ impl WebFileWriter for Dcel {
     }
 }
 
+struct JsNamedCount<'a> {
+    name: &'a str,
+    count: usize,
+}
+
+impl<'a> WebFileWriter for JsNamedCount<'a> {
+    fn write_to_file(&self, file: &mut File, id: usize, level: u32) -> std::io::Result<()> {
+        JsObject::new(&JsValues::new(vec![
+            JsValue::new("name", &self.name),
+            JsValue::new("count", &self.count),
+        ]))
+        .write_to_file(file, id, level)
+    }
+}
+
+struct JsTiming<'a> {
+    name: &'a str,
+    seconds: f64,
+}
+
+impl<'a> WebFileWriter for JsTiming<'a> {
+    fn write_to_file(&self, file: &mut File, id: usize, level: u32) -> std::io::Result<()> {
+        JsObject::new(&JsValues::new(vec![
+            JsValue::new("name", &self.name),
+            JsValue::new("seconds", &self.seconds),
+        ]))
+        .write_to_file(file, id, level)
+    }
+}
+
+impl WebFileWriter for GraphStats {
+    fn write_to_file(&self, file: &mut File, id: usize, level: u32) -> std::io::Result<()> {
+        JsObject::new(&JsValues::new(vec![
+            JsValue::new("vertices", &self.vertices),
+            JsValue::new("edges", &self.edges),
+            JsValue::new("faces", &self.faces),
+        ]))
+        .write_to_file(file, id, level)
+    }
+}
+
+impl WebFileWriter for VertexCover {
+    fn write_to_file(&self, file: &mut File, id: usize, level: u32) -> std::io::Result<()> {
+        JsObject::new(&JsValues::new(vec![
+            JsValue::new("size", &self.vertices.len()),
+            JsValue::new("weight", &self.weight),
+            JsValue::new("lower_bound", &self.lower_bound),
+            JsValue::new("vertices", &JsArray::new(&self.vertices)),
+        ]))
+        .write_to_file(file, id, level)
+    }
+}
+
+/// Writes a [MISResult] as JSON, so that it can be processed by other programs.
+pub struct JsonResultWriter<'a> {
+    result: &'a MISResult,
+}
+
+impl<'a> JsonResultWriter<'a> {
+    pub fn new(result: &'a MISResult) -> Self {
+        JsonResultWriter { result }
+    }
+
+    pub fn write_to<P: AsRef<Path>>(&self, filename: P) -> std::io::Result<()> {
+        let mut file = File::create(filename)?;
+        let result = self.result;
+        let size = result.result.len();
+        let ratio = result.ratio();
+        let total_time = result.total_time.as_secs_f64();
+        let timings: Vec<JsTiming> = result
+            .timings
+            .iter()
+            .map(|(name, duration)| JsTiming {
+                name,
+                seconds: duration.as_secs_f64(),
+            })
+            .collect();
+        let reductions: Vec<JsNamedCount> = result
+            .reductions
+            .iter()
+            .map(|(name, count)| JsNamedCount {
+                name,
+                count: *count,
+            })
+            .collect();
+
+        let problem = result.problem.name();
+        let vertices = JsArray::new(&result.result);
+        let offsets = JsArray::new(&result.offsets);
+        let timings = JsArray::new(&timings);
+        let reductions = JsArray::new(&reductions);
+        let mut values = vec![
+            JsValue::new("problem", &problem),
+            JsValue::new("scheme", &result.scheme),
+            JsValue::new("k", &result.k),
+            JsValue::new("i", &result.i),
+            JsValue::new("root", &result.root),
+            JsValue::new("max_level", &result.max_level),
+            JsValue::new("size", &size),
+            JsValue::new("weight", &result.weight),
+            JsValue::new("guarantee", &result.guarantee),
+            JsValue::new("optimum_bound", &result.optimum_bound),
+            JsValue::new("ratio", &ratio),
+            JsValue::new("partial", &result.partial),
+            JsValue::new("offsets", &offsets),
+            JsValue::new("vertices", &vertices),
+            JsValue::new("total_time", &total_time),
+            JsValue::new("timings", &timings),
+            JsValue::new("graph", &result.graph),
+            JsValue::new("reductions", &reductions),
+        ];
+        if let Some(cover) = &result.cover {
+            values.push(JsValue::new("cover", cover));
+        }
+        JsObject::new(&JsValues::new(values)).write_to_file(&mut file, 0, 0)?;
+        writeln!(file)
+    }
+}
+
 pub struct JsDataWriter<'a> {
     file: File,
     dcel: &'a Dcel,
@@ -387,32 +537,102 @@ pub struct JsDataWriter<'a> {
 }
 
 impl<'a> JsDataWriter<'a> {
-    pub fn new(filename: &str, dcel: &'a Dcel, result: MISResult) -> Self {
-        let file_result = File::create(filename);
-
-        let file = match file_result {
-            Ok(file) => file,
-            Err(error) => panic!("Problem opening the file: {:?}", error),
-        };
-
-        JsDataWriter { file, dcel, result }
+    pub fn new(filename: &str, dcel: &'a Dcel, result: MISResult) -> std::io::Result<Self> {
+        let file = File::create(filename)?;
+        Ok(JsDataWriter { file, dcel, result })
     }
 
-    pub fn write_data(&mut self) {
-        let st = self.dcel.spanning_tree(0);
-        let best_donuts = &self
-            .dcel
-            .find_donuts_for_k(self.result.k, self.result.i, &st)
-            .unwrap();
-        let _ = JsObject {
+    pub fn write_data(&mut self) -> Result<(), Box<dyn Error>> {
+        let dcel = JsDcel {
+            dcel: self.dcel,
+            root: self.result.root,
+        };
+        let st = self.dcel.spanning_tree(self.result.root);
+        /* the donuts of a disconnected graph belong to its components and are not shown */
+        let best_donuts = match self.result.has_donuts && st.is_spanning() {
+            true => self
+                .dcel
+                .find_donuts_for_k(self.result.k, self.result.i, &st)?,
+            false => vec![],
+        };
+        JsObject {
             item: &JsValues {
                 values: vec![
-                    JsValue::new("dcel", self.dcel),
+                    JsValue::new("dcel", &dcel),
                     JsValue::new("result", &self.result),
                     JsValue::new("donuts", &JsArray::new(&best_donuts)),
                 ],
             },
         }
-        .write_to_file(&mut self.file, 0, 0);
+        .write_to_file(&mut self.file, 0, 0)?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::JsonResultWriter;
+    use crate::{GraphStats, MISResult, Problem, VertexCover};
+
+    #[test]
+    fn result_json() {
+        let mut result = MISResult {
+            problem: Problem::IndependentSet,
+            scheme: String::from("ptas"),
+            timings: vec![(String::from("Donut \"0\""), Duration::from_millis(1500))],
+            total_time: Duration::from_secs(2),
+            result: vec![0, 3],
+            weight: 2,
+            guarantee: 0.75,
+            optimum_bound: 4,
+            k: 2,
+            i: 1,
+            root: 3,
+            max_level: 2,
+            has_donuts: true,
+            partial: true,
+            offsets: vec![0, 1],
+            graph: GraphStats {
+                vertices: 4,
+                edges: 5,
+                faces: 3,
+            },
+            reductions: vec![(String::from("twin"), 0)],
+            cover: None,
+            warnings: vec![],
+        };
+        let path = std::env::temp_dir().join("thm_ptas_result.json");
+        let path = path.to_str().unwrap();
+        JsonResultWriter::new(&result).write_to(path).unwrap();
+
+        let json: String = std::fs::read_to_string(path)
+            .unwrap()
+            .split_whitespace()
+            .collect();
+        assert_eq!(
+            json,
+            "{\"problem\":\"independent-set\",\"scheme\":\"ptas\",\"k\":2,\"i\":1,\"root\":3,\"max_level\":2,\"size\":2,\"weight\":2,\
+             \"guarantee\":0.75,\"optimum_bound\":4,\"ratio\":0.5,\"partial\":true,\"offsets\":[0,1],\
+             \"vertices\":[0,3],\
+             \"total_time\":2,\"timings\":[{\"name\":\"Donut\\\"0\\\"\",\"seconds\":1.5}],\
+             \"graph\":{\"vertices\":4,\"edges\":5,\"faces\":3},\
+             \"reductions\":[{\"name\":\"twin\",\"count\":0}]}"
+        );
+
+        result.problem = Problem::VertexCover;
+        result.cover = Some(VertexCover {
+            vertices: vec![1, 2],
+            weight: 2,
+            lower_bound: 1,
+        });
+        JsonResultWriter::new(&result).write_to(path).unwrap();
+        let json = std::fs::read_to_string(path).unwrap();
+        assert!(json.contains("\"problem\": \"vertex-cover\""), "{json}");
+        let json: String = json.split_whitespace().collect();
+        assert!(json.ends_with(
+            "\"cover\":{\"size\":2,\"weight\":2,\"lower_bound\":1,\"vertices\":[1,2]}}"
+        ));
     }
 }
@@ -0,0 +1,463 @@
+use std::collections::HashSet;
+
+use arboretum_td::graph::{HashMapGraph, MutableGraph};
+use arboretum_td::solver::Solver;
+use arboretum_td::tree_decomposition::{Bag, TreeDecomposition};
+use fxhash::FxHashSet;
+
+use super::dcel::spanning_tree::SpanningTree;
+use super::dcel::vertex::VertexId;
+use super::iterators::subset::SubsetIter;
+use super::mis_finder::Weight;
+use super::nice_tree_decomp::NiceTreeDecomposition;
+use super::ntd_problem::{solve_ntd_problem, BagTable, NtdProblem, TableEntry};
+use super::Dcel;
+
+/// The state of a bag vertex in the dynamic table of [DominatingSet].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Domination {
+    /// The vertex is in the dominating set.
+    InSet,
+    /// The vertex is not in the set, but it is dominated by a vertex of the subtree.
+    Dominated,
+    /// The vertex is not in the set and may not be dominated yet, a vertex that is introduced
+    /// later can still dominate it.
+    Undominated,
+}
+
+const STATES: [Domination; 3] = [
+    Domination::InSet,
+    Domination::Dominated,
+    Domination::Undominated,
+];
+
+/* the vertices of a bag in the order of the digits of an assignment */
+fn sorted_vertices(bag: &Bag) -> Vec<VertexId> {
+    let mut vertices: Vec<VertexId> = bag.vertex_set.iter().copied().collect();
+    vertices.sort_unstable();
+    vertices
+}
+
+fn assignments(vertices: &[VertexId]) -> usize {
+    3usize.pow(vertices.len() as u32)
+}
+
+fn decode(mut assignment: usize, len: usize) -> Vec<Domination> {
+    (0..len)
+        .map(|_| {
+            let state = STATES[assignment % 3];
+            assignment /= 3;
+            state
+        })
+        .collect()
+}
+
+fn encode(states: &[Domination]) -> usize {
+    states.iter().rev().fold(0, |assignment, state| {
+        assignment * 3 + STATES.iter().position(|s| s == state).unwrap()
+    })
+}
+
+fn add(left: Option<Weight>, right: Option<Weight>) -> Option<Weight> {
+    Some(left? + right?)
+}
+
+/* returns the better of two entries of the same table */
+fn min_entry(a: (usize, Option<Weight>), b: (usize, Option<Weight>)) -> (usize, Option<Weight>) {
+    match (a.1, b.1) {
+        (None, _) => b,
+        (Some(_), None) => a,
+        (Some(wa), Some(wb)) if wb < wa => b,
+        _ => a,
+    }
+}
+
+type Entry = TableEntry<usize, Option<Weight>>;
+
+/// The minimum (weight) dominating set problem for [solve_ntd_problem].
+///
+/// Every assignment of the three [Domination] states to the sorted bag vertices is encoded as a
+/// number in base 3, the state of the j-th vertex is its j-th digit. The value of an assignment
+/// is the minimum weight of a set that fits it, or `None` if there is no such set.
+pub struct DominatingSet<'a> {
+    adjacency_matrix: &'a [Vec<bool>],
+    weights: &'a [Weight],
+    dominate: &'a [bool],
+}
+
+impl<'a> DominatingSet<'a> {
+    /// Vertices `v` without `dominate[v]` do not have to be dominated.
+    pub fn new(
+        adjacency_matrix: &'a [Vec<bool>],
+        weights: &'a [Weight],
+        dominate: &'a [bool],
+    ) -> Self {
+        DominatingSet {
+            adjacency_matrix,
+            weights,
+            dominate,
+        }
+    }
+
+    fn has_dominator(&self, states: &[Domination], v: VertexId, vertices: &[VertexId]) -> bool {
+        vertices
+            .iter()
+            .zip(states)
+            .any(|(&u, &s)| s == Domination::InSet && self.adjacency_matrix[u][v])
+    }
+
+    fn in_set_weight(&self, states: &[Domination], vertices: &[VertexId]) -> Weight {
+        vertices
+            .iter()
+            .zip(states)
+            .filter(|(_, &s)| s == Domination::InSet)
+            .map(|(&v, _)| self.weights[v])
+            .sum()
+    }
+}
+
+impl NtdProblem for DominatingSet<'_> {
+    type State = usize;
+    type Value = Option<Weight>;
+
+    fn leaf(&self, bag: &Bag) -> Vec<Entry> {
+        // The bag vertices can only be dominated by each other.
+        let vertices = sorted_vertices(bag);
+        (0..assignments(&vertices))
+            .map(|assignment| {
+                let states = decode(assignment, vertices.len());
+                let valid = vertices.iter().zip(&states).all(|(&v, &s)| {
+                    s != Domination::Dominated || self.has_dominator(&states, v, &vertices)
+                });
+                let weight = self.in_set_weight(&states, &vertices);
+                TableEntry::new(assignment, valid.then_some(weight), (None, None))
+            })
+            .collect()
+    }
+
+    fn introduce(
+        &self,
+        bag: &Bag,
+        v: usize,
+        child: &BagTable<usize, Option<Weight>>,
+    ) -> Vec<Entry> {
+        let vertices = sorted_vertices(bag);
+        let p = vertices.binary_search(&v).unwrap();
+        let mut child_vertices = vertices.clone();
+        child_vertices.remove(p);
+
+        (0..assignments(&vertices))
+            .map(|assignment| {
+                let mut states = decode(assignment, vertices.len());
+                let state = states.remove(p);
+                let valid = match state {
+                    Domination::Undominated => true,
+                    Domination::Dominated => self.has_dominator(&states, v, &child_vertices),
+                    Domination::InSet => {
+                        /* v dominates its neighbors, so they need no other dominator */
+                        for (u, s) in child_vertices.iter().zip(states.iter_mut()) {
+                            if *s == Domination::Dominated && self.adjacency_matrix[*u][v] {
+                                *s = Domination::Undominated;
+                            }
+                        }
+                        true
+                    }
+                };
+                if !valid {
+                    return TableEntry::new(assignment, None, (None, None));
+                }
+
+                let extra = match state {
+                    Domination::InSet => self.weights[v],
+                    _ => 0,
+                };
+                let (i, weight) = child.get(&encode(&states));
+                TableEntry::new(assignment, weight.map(|w| w + extra), (Some(i), None))
+            })
+            .collect()
+    }
+
+    fn forget(&self, bag: &Bag, v: usize, child: &BagTable<usize, Option<Weight>>) -> Vec<Entry> {
+        // v has to be dominated unless it is not required.
+        let vertices = sorted_vertices(bag);
+        let p = vertices.partition_point(|&u| u < v);
+        (0..assignments(&vertices))
+            .map(|assignment| {
+                let mut states = decode(assignment, vertices.len());
+                states.insert(p, Domination::InSet);
+                let mut best = (0, None);
+                for state in STATES {
+                    if state == Domination::Undominated && self.dominate[v] {
+                        continue;
+                    }
+                    states[p] = state;
+                    best = min_entry(best, child.get(&encode(&states)));
+                }
+                TableEntry::new(assignment, best.1, (Some(best.0), None))
+            })
+            .collect()
+    }
+
+    fn join(
+        &self,
+        bag: &Bag,
+        left: &BagTable<usize, Option<Weight>>,
+        right: &BagTable<usize, Option<Weight>>,
+    ) -> Vec<Entry> {
+        // Every dominated vertex has to be dominated in one of the subtrees.
+        let vertices = sorted_vertices(bag);
+        (0..assignments(&vertices))
+            .map(|assignment| {
+                let states = decode(assignment, vertices.len());
+                let in_set = self.in_set_weight(&states, &vertices);
+                let dominated: FxHashSet<usize> = states
+                    .iter()
+                    .enumerate()
+                    .filter(|(_, &s)| s == Domination::Dominated)
+                    .map(|(p, _)| p)
+                    .collect();
+
+                let mut best: Option<(usize, usize, Weight)> = None;
+                for in_left in SubsetIter::new(&dominated) {
+                    let mut left_states = states.clone();
+                    let mut right_states = states.clone();
+                    for &p in &dominated {
+                        match in_left.contains(&p) {
+                            true => right_states[p] = Domination::Undominated,
+                            false => left_states[p] = Domination::Undominated,
+                        }
+                    }
+                    let (i, left_weight) = left.get(&encode(&left_states));
+                    let (j, right_weight) = right.get(&encode(&right_states));
+                    if let Some(weight) = add(left_weight, right_weight) {
+                        let weight = weight - in_set;
+                        if best.is_none_or(|(_, _, w)| weight < w) {
+                            best = Some((i, j, weight));
+                        }
+                    }
+                }
+                match best {
+                    Some((i, j, weight)) => {
+                        TableEntry::new(assignment, Some(weight), (Some(i), Some(j)))
+                    }
+                    None => TableEntry::new(assignment, None, (None, None)),
+                }
+            })
+            .collect()
+    }
+
+    fn best_root_entry(&self, bag: &Bag, table: &BagTable<usize, Option<Weight>>) -> Option<usize> {
+        /* every required vertex of the root bag has to be dominated */
+        let vertices = sorted_vertices(bag);
+        let (i, weight) = table
+            .entries()
+            .iter()
+            .enumerate()
+            .filter(|(_, entry)| {
+                let states = decode(entry.state, vertices.len());
+                vertices
+                    .iter()
+                    .zip(states)
+                    .all(|(&v, s)| s != Domination::Undominated || !self.dominate[v])
+            })
+            .fold((0, None), |best, (i, entry)| {
+                min_entry(best, (i, entry.value))
+            });
+        weight.map(|_| i)
+    }
+
+    fn reconstruct(&self, bag: &Bag, state: &usize, solution: &mut HashSet<usize>) {
+        let vertices = sorted_vertices(bag);
+        let states = decode(*state, vertices.len());
+        for (&v, s) in vertices.iter().zip(states) {
+            if s == Domination::InSet {
+                solution.insert(v);
+            }
+        }
+    }
+}
+
+/// Finds a minimum weight set of vertices of the nice tree decomposition `ntd` that dominates
+/// every vertex `v` with `dominate[v]`, i.e. `v` is in the set or has a neighbor in it.
+/// Vertices that do not have to be dominated can still be used to dominate others, which is
+/// needed for the overlapping donuts of the PTAS, see [find_overlapping_donuts].
+/// Returns the set and its weight, see [DominatingSet].
+pub fn find_dominating_set(
+    adjacency_matrix: &[Vec<bool>],
+    weights: &[Weight],
+    dominate: &[bool],
+    ntd: &NiceTreeDecomposition,
+) -> (HashSet<VertexId>, Weight) {
+    let problem = DominatingSet::new(adjacency_matrix, weights, dominate);
+    /* every vertex can dominate itself, so there is always a solution */
+    let (set, _) = solve_ntd_problem(&problem, ntd).unwrap();
+    let weight = set.iter().map(|&v| weights[v]).sum();
+    (set, weight)
+}
+
+/// Finds a minimum weight dominating set by checking all subsets of the graph.
+/// It takes exponential time, so it only checks the dynamic program in the tests.
+#[cfg(test)]
+pub fn find_dominating_set_exhaustive(
+    adjacency_matrix: &[Vec<bool>],
+    weights: &[Weight],
+) -> (HashSet<VertexId>, Weight) {
+    let n = adjacency_matrix.len();
+    let mut min: HashSet<VertexId> = (0..n).collect();
+    let mut min_weight: Weight = weights.iter().sum();
+    for subset in SubsetIter::new(&FxHashSet::from_iter(0..n)) {
+        let weight = subset.iter().map(|&v| weights[v]).sum();
+        if weight < min_weight && find_undominated_vertex(adjacency_matrix, &subset).is_none() {
+            min = subset.into_iter().collect();
+            min_weight = weight;
+        }
+    }
+    (min, min_weight)
+}
+
+/// Returns a vertex that is neither in `set` nor adjacent to a vertex of it.
+pub fn find_undominated_vertex<'a>(
+    adjacency_matrix: &[Vec<bool>],
+    set: impl IntoIterator<Item = &'a VertexId> + Copy,
+) -> Option<VertexId> {
+    let mut dominated = vec![false; adjacency_matrix.len()];
+    for &u in set {
+        dominated[u] = true;
+        for (v, &adjacent) in adjacency_matrix[u].iter().enumerate() {
+            dominated[v] |= adjacent;
+        }
+    }
+    dominated.iter().position(|&d| !d)
+}
+
+/// The vertices of the levels `first..=last` of the spanning tree, extended by one level on each
+/// side. The extra levels may be used to dominate the inner levels, but they are dominated by the
+/// neighboring donuts.
+pub struct OverlappingDonut {
+    /// Sorted vertices of the donut including the extra levels.
+    pub vertices: Vec<VertexId>,
+    /// Indexed by the global vertex id, true for the vertices of the inner levels.
+    pub dominate: Vec<bool>,
+}
+
+impl OverlappingDonut {
+    /// A single donut of the whole graph, whose vertices all have to be dominated.
+    pub fn whole_graph(vertex_count: usize) -> Self {
+        OverlappingDonut {
+            vertices: (0..vertex_count).collect(),
+            dominate: vec![true; vertex_count],
+        }
+    }
+
+    /// Computes a tree decomposition of the subgraph induced by the donut with the solvers of
+    /// arboretum, or `None` if the donut has no vertices.
+    pub fn tree_decomposition(&self, graph: &Dcel) -> Option<TreeDecomposition> {
+        let mut in_donut = vec![false; graph.num_vertices()];
+        self.vertices.iter().for_each(|&v| in_donut[v] = true);
+
+        let mut hash_map_graph = HashMapGraph::new();
+        for &u in &self.vertices {
+            hash_map_graph.add_vertex(u);
+            for v in graph.neighbors(u) {
+                if in_donut[v] {
+                    hash_map_graph.add_edge(u, v);
+                }
+            }
+        }
+        let td = Solver::auto(&hash_map_graph).solve(&hash_map_graph);
+        (!td.bags.is_empty()).then_some(td)
+    }
+}
+
+/// Splits the levels of the spanning tree into donuts like [Dcel::find_donuts_for_k], so that a
+/// donut ends at every level `n` with `n % (k + 1) == i`, and adds one level on each side.
+///
+/// Taking the dominating sets of all donuts together dominates the whole graph. The levels next
+/// to a border belong to two donuts, so the best of the `k + 1` offsets is at most
+/// `1 + 2 / (k + 1)` times as heavy as the minimum dominating set.
+pub fn find_overlapping_donuts(
+    spanning_tree: &SpanningTree,
+    k: usize,
+    i: usize,
+) -> Vec<OverlappingDonut> {
+    let levels = spanning_tree.vertex_level();
+    let max_level = spanning_tree.max_level();
+
+    let mut borders: Vec<usize> = (0..=max_level).filter(|n| n % (k + 1) == i).collect();
+    if borders.last() != Some(&max_level) {
+        borders.push(max_level);
+    }
+
+    let mut first: usize = 0;
+    let mut result = vec![];
+    for last in borders {
+        let outer = first.saturating_sub(1)..=last + 1;
+        result.push(OverlappingDonut {
+            vertices: (0..levels.len())
+                .filter(|&v| outer.contains(&levels[v]))
+                .collect(),
+            dominate: levels.iter().map(|l| (first..=last).contains(l)).collect(),
+        });
+        first = last + 1;
+    }
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        find_dominating_set, find_dominating_set_exhaustive, find_overlapping_donuts,
+        find_undominated_vertex,
+    };
+    use crate::graph::nice_tree_decomp::NiceTreeDecomposition;
+    use crate::read_graph_file_into_dcel_builder;
+
+    #[test]
+    fn dominating_set() {
+        for file in ["tree", "tri", "simple", "bsp3"] {
+            let graph = read_graph_file_into_dcel_builder(&format!("data/{file}.graph"))
+                .unwrap()
+                .build();
+            let adjacency = graph.adjacency_matrix();
+            let weights: Vec<usize> = (0..graph.num_vertices()).map(|v| v % 3 + 1).collect();
+            let spanning_tree = graph.spanning_tree(0);
+
+            /* a single donut contains the whole graph */
+            let k = spanning_tree.max_level() + 1;
+            let donuts = find_overlapping_donuts(&spanning_tree, k, k);
+            assert_eq!(donuts.len(), 1);
+            assert!(donuts[0].dominate.iter().all(|&d| d));
+
+            let td = donuts[0].tree_decomposition(&graph).unwrap();
+            let ntd = NiceTreeDecomposition::from(&td);
+            let (set, weight) =
+                find_dominating_set(&adjacency, &weights, &donuts[0].dominate, &ntd);
+            assert_eq!(find_undominated_vertex(&adjacency, &set), None);
+            assert_eq!(weight, set.iter().map(|&v| weights[v]).sum::<usize>());
+            if graph.num_vertices() <= 16 {
+                let (_, optimum) = find_dominating_set_exhaustive(&adjacency, &weights);
+                assert_eq!(weight, optimum, "{file}");
+            }
+        }
+    }
+
+    #[test]
+    fn overlapping_donuts() {
+        let graph = read_graph_file_into_dcel_builder("data/bsp3.graph")
+            .unwrap()
+            .build();
+        let spanning_tree = graph.spanning_tree(0);
+        let levels = spanning_tree.vertex_level();
+        for i in 0..=2 {
+            let donuts = find_overlapping_donuts(&spanning_tree, 2, i);
+            /* every vertex is dominated by exactly one donut that contains all its neighbors */
+            for v in 0..graph.num_vertices() {
+                let owners: Vec<_> = donuts.iter().filter(|d| d.dominate[v]).collect();
+                assert_eq!(owners.len(), 1, "level {}", levels[v]);
+                for u in graph.neighbors(v) {
+                    assert!(owners[0].vertices.contains(&u));
+                }
+            }
+        }
+    }
+}
@@ -1,2 +1 @@
-pub mod dt_fast;
-pub mod dt_normal;
+pub mod dt_dense;
@@ -0,0 +1,137 @@
+/* Dynamic table with fixed bag-local positions:
+ * - The i-th smallest vertex of a bag is bit i of a subset mask.
+ * - The table of a bag is a vector with one entry per mask, so no hashing is needed.
+ */
+
+use fxhash::FxHashSet;
+
+use crate::graph::mis_finder::{MisSize, Weight};
+
+/// Masks are `u64`, so bags with more vertices cannot be stored.
+pub const MAX_BAG_SIZE: usize = u64::BITS as usize - 1;
+
+/// Returns the number of bytes of the table of a bag with `bag_size` vertices, or `usize::MAX` if
+/// it cannot even be addressed.
+pub fn table_bytes(bag_size: usize) -> usize {
+    let entry = std::mem::size_of::<MisSize>();
+    match bag_size < usize::BITS as usize {
+        true => entry.saturating_mul(1 << bag_size),
+        false => usize::MAX,
+    }
+}
+
+/// Returns whether the table of a bag with `bag_size` vertices can be allocated. The allocation
+/// is tried, so that a table that does not fit into memory fails here instead of aborting the
+/// process once the dynamic program reaches the bag.
+pub fn can_allocate_table(bag_size: usize) -> bool {
+    bag_size <= MAX_BAG_SIZE
+        && table_bytes(bag_size) <= isize::MAX as usize
+        && Vec::<MisSize>::new()
+            .try_reserve_exact(1 << bag_size)
+            .is_ok()
+}
+
+/// The table of a single bag. Entry `sizes[mask]` is the maximum independent set size for the
+/// subset of [DenseBag::vertices] given by the bits of `mask`.
+#[derive(Debug, Clone)]
+pub struct DenseBag {
+    /// The sorted vertices of the bag, the index of a vertex is its bit in the masks.
+    pub vertices: Vec<usize>,
+    pub sizes: Vec<MisSize>,
+}
+
+impl DenseBag {
+    /// Creates an empty table for the vertices of a bag, the [DenseBag::sizes] of all
+    /// [DenseBag::subset_count] subsets have to be filled in afterwards.
+    /// The bag may have at most [MAX_BAG_SIZE] vertices.
+    pub fn new(vertex_set: &FxHashSet<usize>) -> Self {
+        assert!(vertex_set.len() <= MAX_BAG_SIZE);
+        let mut vertices: Vec<usize> = vertex_set.iter().copied().collect();
+        vertices.sort_unstable();
+        DenseBag {
+            vertices,
+            sizes: vec![],
+        }
+    }
+
+    /// Returns the number of subsets of the bag.
+    pub fn subset_count(&self) -> usize {
+        1 << self.vertices.len()
+    }
+
+    /// Returns the bit of `v` in the masks of this bag. Panics if `v` is not in the bag.
+    pub fn position(&self, v: usize) -> usize {
+        self.vertices.binary_search(&v).unwrap()
+    }
+
+    /// Returns the mask of the neighbors of `v` in this bag.
+    pub fn neighbor_mask(&self, v: usize, adjaceny_matrix: &[Vec<bool>]) -> u64 {
+        self.vertices
+            .iter()
+            .enumerate()
+            .filter(|(_, &u)| adjaceny_matrix[u][v])
+            .fold(0, |mask, (i, _)| mask | 1 << i)
+    }
+
+    /// Returns the total weight of every subset, indexed by its mask.
+    pub fn subset_weights(&self, weights: &[Weight]) -> Vec<Weight> {
+        let mut result = vec![0; self.subset_count()];
+        for mask in 1..self.subset_count() {
+            let lowest = mask.trailing_zeros() as usize;
+            result[mask] = result[mask & (mask - 1)] + weights[self.vertices[lowest]];
+        }
+        result
+    }
+
+    /// Returns the vertices of the subset given by `mask`.
+    pub fn subset(&self, mask: u64) -> impl Iterator<Item = usize> + '_ {
+        self.vertices
+            .iter()
+            .enumerate()
+            .filter(move |(i, _)| mask & 1 << i != 0)
+            .map(|(_, &v)| v)
+    }
+}
+
+/// Removes bit `p` from `mask` and moves all higher bits one position down.
+/// This maps the masks of a bag to the masks of a child that does not contain the vertex at `p`.
+pub fn remove_bit(mask: u64, p: usize) -> u64 {
+    let low = (1 << p) - 1;
+    (mask & low) | ((mask >> 1) & !low)
+}
+
+/// Inserts an unset bit at `p` into `mask` and moves all higher bits one position up.
+/// This is the inverse of [remove_bit] for masks without bit `p`.
+pub fn insert_bit(mask: u64, p: usize) -> u64 {
+    let low = (1 << p) - 1;
+    (mask & low) | ((mask & !low) << 1)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{can_allocate_table, insert_bit, remove_bit, table_bytes, DenseBag};
+    use crate::graph::mis_finder::MisSize;
+    use fxhash::FxHashSet;
+
+    #[test]
+    fn masks() {
+        assert_eq!(remove_bit(0b1011, 1), 0b101);
+        assert_eq!(remove_bit(0b1011, 2), 0b111);
+        assert_eq!(insert_bit(0b101, 1), 0b1001);
+        for mask in 0..64 {
+            for p in 0..6 {
+                assert_eq!(remove_bit(insert_bit(mask, p), p), mask);
+            }
+        }
+
+        let bag = DenseBag::new(&FxHashSet::from_iter([7, 2, 5]));
+        assert_eq!(bag.vertices, vec![2, 5, 7]);
+        assert_eq!(bag.subset(0b101).collect::<Vec<_>>(), vec![2, 7]);
+        assert_eq!(bag.subset_weights(&[1; 8])[0b111], 3);
+        assert_eq!(table_bytes(3), 8 * std::mem::size_of::<MisSize>());
+        assert!(can_allocate_table(3));
+        assert!(!can_allocate_table(50));
+        assert!(!can_allocate_table(64));
+        assert_eq!(table_bytes(64), usize::MAX);
+    }
+}
@@ -0,0 +1,970 @@
+use std::fs::File;
+use std::io::{self, BufRead};
+use std::path::Path;
+
+use super::dcel::vertex::VertexId;
+use super::mis_finder::Weight;
+use super::planarity::{planar_embedding, NotPlanar};
+use super::quick_graph::QuickGraph;
+use super::DcelBuilder;
+
+/// Represents the problems that can occur while reading a graph file.
+/// Line numbers start at 1.
+#[derive(Debug)]
+pub enum GraphParseError {
+    /// The file could not be opened or read.
+    Io { path: String, source: io::Error },
+
+    /// The file ended before the given header line (number of vertices or edges) was read.
+    MissingHeader { line: usize, header: &'static str },
+
+    /// The file ended before all `2 * <Number of edges>` arcs were read.
+    TooFewArcs {
+        line: usize,
+        expected: usize,
+        found: usize,
+    },
+
+    /// The number of edges in the header is too large, its arcs cannot be counted.
+    TooManyEdges { line: usize, edge_count: usize },
+
+    /// The number of vertices is larger than the size of the file in bytes. Isolated vertices
+    /// need no line, so a count like this can only come from a broken header.
+    TooManyVertices {
+        line: usize,
+        vertex_count: usize,
+        file_size: u64,
+    },
+
+    /// An arc line does not contain a source and a target vertex.
+    MissingVertex { line: usize },
+
+    /// A token is not a non-negative integer.
+    InvalidToken { line: usize, token: String },
+
+    /// A vertex id is not smaller than the number of vertices given in the header.
+    VertexOutOfRange {
+        line: usize,
+        token: String,
+        vertex_count: usize,
+    },
+
+    /// The file ended before the adjacency line of the vertex (starting at 0) was read.
+    MissingAdjacency { line: usize, vertex: VertexId },
+
+    /// The file contains vertex weights, but the weight of the vertex (starting at 0) is missing.
+    MissingWeight { line: usize, vertex: VertexId },
+
+    /// The file contains no rotation system and the graph has no planar embedding.
+    NotPlanar(NotPlanar),
+}
+
+impl GraphParseError {
+    /// Returns the line the error occurred in, if the error belongs to a line.
+    pub fn line(&self) -> Option<usize> {
+        match self {
+            GraphParseError::Io { .. } | GraphParseError::NotPlanar(_) => None,
+            GraphParseError::MissingHeader { line, .. }
+            | GraphParseError::TooFewArcs { line, .. }
+            | GraphParseError::TooManyEdges { line, .. }
+            | GraphParseError::TooManyVertices { line, .. }
+            | GraphParseError::MissingVertex { line }
+            | GraphParseError::InvalidToken { line, .. }
+            | GraphParseError::VertexOutOfRange { line, .. }
+            | GraphParseError::MissingAdjacency { line, .. }
+            | GraphParseError::MissingWeight { line, .. } => Some(*line),
+        }
+    }
+}
+
+impl std::error::Error for GraphParseError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            GraphParseError::Io { source, .. } => Some(source),
+            GraphParseError::NotPlanar(error) => Some(error),
+            _ => None,
+        }
+    }
+}
+
+impl std::fmt::Display for GraphParseError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            GraphParseError::Io { path, source } => write!(f, "could not read {path}: {source}"),
+            GraphParseError::MissingHeader { line, header } => {
+                write!(f, "line {line}: missing header `{header}`")
+            }
+            GraphParseError::TooFewArcs {
+                line,
+                expected,
+                found,
+            } => write!(
+                f,
+                "line {line}: expected {expected} arcs but the file ends after {found}"
+            ),
+            GraphParseError::TooManyEdges { line, edge_count } => {
+                write!(f, "line {line}: {edge_count} edges are too many")
+            }
+            GraphParseError::TooManyVertices {
+                line,
+                vertex_count,
+                file_size,
+            } => write!(
+                f,
+                "line {line}: {vertex_count} vertices are more than a file of {file_size} bytes can contain"
+            ),
+            GraphParseError::MissingVertex { line } => {
+                write!(f, "line {line}: an arc needs a source and a target vertex")
+            }
+            GraphParseError::InvalidToken { line, token } => {
+                write!(f, "line {line}: `{token}` is not a non-negative integer")
+            }
+            GraphParseError::VertexOutOfRange {
+                line,
+                token,
+                vertex_count,
+            } => write!(
+                f,
+                "line {line}: vertex id `{token}` is out of range, the graph has {vertex_count} vertices"
+            ),
+            GraphParseError::MissingAdjacency { line, vertex } => write!(
+                f,
+                "line {line}: the file ends before the adjacency of vertex {vertex}"
+            ),
+            GraphParseError::MissingWeight { line, vertex } => write!(
+                f,
+                "line {line}: the weight of vertex {vertex} is missing"
+            ),
+            GraphParseError::NotPlanar(error) => write!(f, "{error}"),
+        }
+    }
+}
+
+/// Reads the lines of a graph file and keeps track of the current line number, so that all
+/// errors can point to the offending line.
+struct GraphFileLines {
+    lines: io::Lines<io::BufReader<File>>,
+    path: String,
+    line: usize,
+    /// The size of the file in bytes, or `u64::MAX` if it is not a regular file.
+    size: u64,
+}
+
+impl GraphFileLines {
+    fn open<P: AsRef<Path>>(filename: P) -> Result<Self, GraphParseError> {
+        let path = filename.as_ref().display().to_string();
+        match File::open(filename) {
+            Ok(file) => {
+                let size = match file.metadata() {
+                    Ok(metadata) if metadata.is_file() => metadata.len(),
+                    _ => u64::MAX,
+                };
+                Ok(GraphFileLines {
+                    lines: io::BufReader::new(file).lines(),
+                    path,
+                    line: 0,
+                    size,
+                })
+            }
+            Err(source) => Err(GraphParseError::Io { path, source }),
+        }
+    }
+
+    /* checks the vertex count of the given line against the size of the file, since storage
+     * is allocated for every vertex before the file is known to contain the graph */
+    fn vertex_count(&self, vertex_count: usize, line: usize) -> Result<usize, GraphParseError> {
+        match u64::try_from(vertex_count) {
+            Ok(count) if count <= self.size => Ok(vertex_count),
+            _ => Err(GraphParseError::TooManyVertices {
+                line,
+                vertex_count,
+                file_size: self.size,
+            }),
+        }
+    }
+
+    /* returns the next line or None at the end of the file */
+    fn next_line(&mut self) -> Result<Option<String>, GraphParseError> {
+        match self.lines.next() {
+            Some(Ok(line)) => {
+                self.line += 1;
+                Ok(Some(line))
+            }
+            Some(Err(source)) => Err(GraphParseError::Io {
+                path: self.path.clone(),
+                source,
+            }),
+            None => Ok(None),
+        }
+    }
+
+    /* reads a header line that consists of a single number */
+    fn header(&mut self, header: &'static str) -> Result<usize, GraphParseError> {
+        match self.next_line()? {
+            Some(content) => match content.split_whitespace().next() {
+                Some(token) => parse_number(token, self.line),
+                None => Err(GraphParseError::MissingHeader {
+                    line: self.line,
+                    header,
+                }),
+            },
+            None => Err(GraphParseError::MissingHeader {
+                line: self.line + 1,
+                header,
+            }),
+        }
+    }
+
+    /* reads the arc with the given index (starting at 0) of `expected` arcs */
+    fn arc(
+        &mut self,
+        index: usize,
+        expected: usize,
+        vertex_count: usize,
+    ) -> Result<(VertexId, VertexId), GraphParseError> {
+        let content = match self.next_line()? {
+            Some(content) => content,
+            None => {
+                return Err(GraphParseError::TooFewArcs {
+                    line: self.line + 1,
+                    expected,
+                    found: index,
+                })
+            }
+        };
+
+        let mut tokens = content.split_whitespace();
+        match (tokens.next(), tokens.next()) {
+            (Some(src), Some(dst)) => Ok((
+                parse_vertex(src, self.line, vertex_count)?,
+                parse_vertex(dst, self.line, vertex_count)?,
+            )),
+            _ => Err(GraphParseError::MissingVertex { line: self.line }),
+        }
+    }
+}
+
+fn parse_number(token: &str, line: usize) -> Result<usize, GraphParseError> {
+    token.parse().map_err(|_| GraphParseError::InvalidToken {
+        line,
+        token: token.to_string(),
+    })
+}
+
+fn parse_vertex(
+    token: &str,
+    line: usize,
+    vertex_count: usize,
+) -> Result<VertexId, GraphParseError> {
+    let vertex = parse_number(token, line)?;
+    if vertex >= vertex_count {
+        return Err(GraphParseError::VertexOutOfRange {
+            line,
+            token: token.to_string(),
+            vertex_count,
+        });
+    }
+    Ok(vertex)
+}
+
+/* parses a vertex id of a format that counts vertices from 1 */
+fn parse_one_based_vertex(
+    token: &str,
+    line: usize,
+    vertex_count: usize,
+) -> Result<VertexId, GraphParseError> {
+    let vertex = parse_number(token, line)?;
+    if vertex == 0 || vertex > vertex_count {
+        return Err(GraphParseError::VertexOutOfRange {
+            line,
+            token: token.to_string(),
+            vertex_count,
+        });
+    }
+    Ok(vertex - 1)
+}
+
+/// The arcs of an embedding in the order they appear in the graph file. Every other
+/// representation of the input graph is derived from this list, so the file only has to be
+/// parsed once.
+#[derive(Clone, Debug, Default)]
+pub struct ArcList {
+    pub vertex_count: usize,
+    pub arcs: Vec<(VertexId, VertexId)>,
+    /// The weight of each vertex, if the file contains vertex weights.
+    pub weights: Option<Vec<Weight>>,
+}
+
+impl ArcList {
+    pub fn edge_count(&self) -> usize {
+        self.arcs.len() / 2
+    }
+}
+
+impl From<&ArcList> for DcelBuilder {
+    fn from(arc_list: &ArcList) -> Self {
+        let mut dcel_builder = DcelBuilder::with_vertex_count(arc_list.vertex_count);
+        for &(u, v) in &arc_list.arcs {
+            dcel_builder.push_arc(u, v);
+        }
+        dcel_builder
+    }
+}
+
+impl From<&ArcList> for QuickGraph {
+    fn from(arc_list: &ArcList) -> Self {
+        let mut graph = QuickGraph::new(arc_list.vertex_count);
+        graph.edge_count = arc_list.edge_count();
+        for &(u, v) in &arc_list.arcs {
+            if let Some(ref mut adjacency_u) = &mut graph.adjacency[u] {
+                adjacency_u.push(v);
+            }
+        }
+        graph
+    }
+}
+
+pub fn read_graph_file_into_arc_list<P: AsRef<Path>>(
+    filename: P,
+) -> Result<ArcList, GraphParseError> {
+    let mut lines = GraphFileLines::open(filename)?;
+
+    let vertex_count = lines.header("number of vertices")?;
+    let edge_count = lines.header("number of edges")?;
+    let vertex_count = lines.vertex_count(vertex_count, 1)?;
+
+    /* read in arcs, the counts of the header are not trusted for preallocation */
+    let arc_count = edge_count
+        .checked_mul(2)
+        .ok_or(GraphParseError::TooManyEdges {
+            line: lines.line,
+            edge_count,
+        })?;
+    let mut arcs = vec![];
+    for index in 0..arc_count {
+        arcs.push(lines.arc(index, arc_count, vertex_count)?);
+    }
+
+    /* the arcs may be followed by one weight per vertex */
+    let mut weights = None;
+    while let Some(content) = lines.next_line()? {
+        if let Some(token) = content.split_whitespace().next() {
+            weights = Some(read_weights(&mut lines, token, vertex_count)?);
+            break;
+        }
+    }
+
+    Ok(ArcList {
+        vertex_count,
+        arcs,
+        weights,
+    })
+}
+
+/* reads the weights of all vertices, `first` is the weight of vertex 0 */
+fn read_weights(
+    lines: &mut GraphFileLines,
+    first: &str,
+    vertex_count: usize,
+) -> Result<Vec<Weight>, GraphParseError> {
+    let mut weights = vec![parse_number(first, lines.line)?];
+    while weights.len() < vertex_count {
+        let content = lines.next_line()?;
+        match content.as_deref().and_then(|c| c.split_whitespace().next()) {
+            Some(token) => weights.push(parse_number(token, lines.line)?),
+            None => {
+                return Err(GraphParseError::MissingWeight {
+                    line: lines.line + content.is_none() as usize,
+                    vertex: weights.len(),
+                })
+            }
+        }
+    }
+    Ok(weights)
+}
+
+/// The file formats graphs can be read from and written to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, clap::ValueEnum)]
+pub enum GraphFormat {
+    /// `<n>`, `<m>` and the `2m` arcs of a planar embedding in counterclockwise order, optionally
+    /// followed by one `<weight>` line per vertex.
+    Embedding,
+    /// DIMACS `.col`/`.clq`: `p edge <n> <m>` and `e <u> <v>` lines, vertices start at 1.
+    /// Vertex weights are given by `n <v> <weight>` lines.
+    Dimacs,
+    /// METIS: `<n> <m>` and the neighbors of each vertex in its own line, vertices start at 1.
+    /// With `fmt` 10 or 11, the first vertex weight is used.
+    Metis,
+    /// PACE `.gr`: `p <problem> <n> <m>` and `<u> <v>` lines, vertices start at 1.
+    Pace,
+    /// One `<u> <v>` line per edge, vertices start at 0.
+    EdgeList,
+}
+
+impl GraphFormat {
+    /// Guesses the format of a file by its extension. `.graph` files are embeddings, since
+    /// this is the format of our datasets, METIS files have to be selected explicitly.
+    pub fn from_path<P: AsRef<Path>>(path: P) -> GraphFormat {
+        let extension = path
+            .as_ref()
+            .extension()
+            .and_then(|extension| extension.to_str())
+            .map(|extension| extension.to_lowercase());
+        match extension.as_deref() {
+            Some("col") | Some("clq") | Some("dimacs") => GraphFormat::Dimacs,
+            Some("metis") => GraphFormat::Metis,
+            Some("gr") => GraphFormat::Pace,
+            Some("txt") | Some("edges") | Some("el") => GraphFormat::EdgeList,
+            _ => GraphFormat::Embedding,
+        }
+    }
+
+    /// Returns true if files of this format contain a rotation system.
+    pub fn is_embedded(&self) -> bool {
+        matches!(self, GraphFormat::Embedding)
+    }
+}
+
+/// An undirected graph without a rotation system.
+#[derive(Clone, Debug, Default)]
+pub struct EdgeList {
+    pub vertex_count: usize,
+    pub edges: Vec<(VertexId, VertexId)>,
+    /// The weight of each vertex, if the file contains vertex weights.
+    pub weights: Option<Vec<Weight>>,
+}
+
+impl From<&ArcList> for EdgeList {
+    fn from(arc_list: &ArcList) -> Self {
+        EdgeList {
+            vertex_count: arc_list.vertex_count,
+            edges: arc_list
+                .arcs
+                .iter()
+                .filter(|(u, v)| u < v)
+                .copied()
+                .collect(),
+            weights: arc_list.weights.clone(),
+        }
+    }
+}
+
+/// Reads a graph file of the given format. If the format contains no rotation system, the
+/// graph is embedded with [planar_embedding].
+pub fn read_graph_file<P: AsRef<Path>>(
+    filename: P,
+    format: GraphFormat,
+) -> Result<ArcList, GraphParseError> {
+    let edge_list = match format {
+        GraphFormat::Embedding => return read_graph_file_into_arc_list(filename),
+        GraphFormat::Dimacs => read_dimacs_file(filename)?,
+        GraphFormat::Metis => read_metis_file(filename)?,
+        GraphFormat::Pace => read_pace_file(filename)?,
+        GraphFormat::EdgeList => read_edge_list_file(filename)?,
+    };
+    let mut arc_list = planar_embedding(edge_list.vertex_count, &edge_list.edges)
+        .map_err(GraphParseError::NotPlanar)?;
+    arc_list.weights = edge_list.weights;
+    Ok(arc_list)
+}
+
+pub fn read_dimacs_file<P: AsRef<Path>>(filename: P) -> Result<EdgeList, GraphParseError> {
+    read_one_based_edges(filename, Some("e"))
+}
+
+pub fn read_pace_file<P: AsRef<Path>>(filename: P) -> Result<EdgeList, GraphParseError> {
+    read_one_based_edges(filename, None)
+}
+
+/* reads DIMACS and PACE files, which only differ in the prefix of the edge lines */
+fn read_one_based_edges<P: AsRef<Path>>(
+    filename: P,
+    edge_prefix: Option<&str>,
+) -> Result<EdgeList, GraphParseError> {
+    let mut lines = GraphFileLines::open(filename)?;
+    let mut vertex_count = None;
+    let mut edges = vec![];
+    let mut weights: Option<Vec<Weight>> = None;
+
+    while let Some(content) = lines.next_line()? {
+        let mut tokens = content.split_whitespace();
+        let first = match tokens.next() {
+            /* skip empty lines and comments */
+            None => continue,
+            Some(token) if token.starts_with('c') => continue,
+            Some(token) => token,
+        };
+
+        if first == "p" {
+            /* p <problem> <vertices> <edges> */
+            match (tokens.next(), tokens.next(), tokens.next()) {
+                (Some(_), Some(n), Some(m)) => {
+                    let n = parse_number(n, lines.line)?;
+                    vertex_count = Some(lines.vertex_count(n, lines.line)?);
+                    /* the edge count is checked, but not trusted for preallocation */
+                    parse_number(m, lines.line)?;
+                }
+                _ => {
+                    return Err(GraphParseError::MissingHeader {
+                        line: lines.line,
+                        header: "p <problem> <number of vertices> <number of edges>",
+                    })
+                }
+            }
+            continue;
+        }
+
+        let vertex_count = match vertex_count {
+            Some(vertex_count) => vertex_count,
+            None => {
+                return Err(GraphParseError::MissingHeader {
+                    line: lines.line,
+                    header: "p <problem> <number of vertices> <number of edges>",
+                })
+            }
+        };
+        if first == "n" && edge_prefix.is_some() {
+            /* n <vertex> <weight>, vertices without a weight line have weight 1 */
+            match (tokens.next(), tokens.next()) {
+                (Some(v), Some(weight)) => {
+                    let v = parse_one_based_vertex(v, lines.line, vertex_count)?;
+                    weights.get_or_insert_with(|| vec![1; vertex_count])[v] =
+                        parse_number(weight, lines.line)?;
+                }
+                _ => return Err(GraphParseError::MissingVertex { line: lines.line }),
+            }
+            continue;
+        }
+        let src = match edge_prefix {
+            Some(prefix) if first == prefix => tokens.next(),
+            Some(_) => {
+                return Err(GraphParseError::InvalidToken {
+                    line: lines.line,
+                    token: first.to_string(),
+                })
+            }
+            None => Some(first),
+        };
+        match (src, tokens.next()) {
+            (Some(u), Some(v)) => edges.push((
+                parse_one_based_vertex(u, lines.line, vertex_count)?,
+                parse_one_based_vertex(v, lines.line, vertex_count)?,
+            )),
+            _ => return Err(GraphParseError::MissingVertex { line: lines.line }),
+        }
+    }
+
+    match vertex_count {
+        Some(vertex_count) => Ok(EdgeList {
+            vertex_count,
+            edges,
+            weights,
+        }),
+        None => Err(GraphParseError::MissingHeader {
+            line: lines.line + 1,
+            header: "p <problem> <number of vertices> <number of edges>",
+        }),
+    }
+}
+
+pub fn read_metis_file<P: AsRef<Path>>(filename: P) -> Result<EdgeList, GraphParseError> {
+    let mut lines = GraphFileLines::open(filename)?;
+    let header = "<number of vertices> <number of edges> [<fmt> [<ncon>]]";
+
+    /* the header is the first line that is not a comment */
+    let header_line = loop {
+        match lines.next_line()? {
+            Some(content) if content.trim_start().starts_with('%') => continue,
+            Some(content) if content.trim().is_empty() => continue,
+            Some(content) => break content,
+            None => {
+                return Err(GraphParseError::MissingHeader {
+                    line: lines.line + 1,
+                    header,
+                })
+            }
+        }
+    };
+    let header_tokens: Vec<&str> = header_line.split_whitespace().collect();
+    if header_tokens.len() < 2 {
+        return Err(GraphParseError::MissingHeader {
+            line: lines.line,
+            header,
+        });
+    }
+    let vertex_count = parse_number(header_tokens[0], lines.line)?;
+    /* the edge count is checked, but not trusted for preallocation */
+    parse_number(header_tokens[1], lines.line)?;
+
+    /* fmt is a binary number: 1 = edge weights, 10 = vertex weights, 100 = vertex sizes */
+    let fmt = header_tokens.get(2).copied().unwrap_or("0");
+    if fmt.len() > 3 || !fmt.chars().all(|c| c == '0' || c == '1') {
+        return Err(GraphParseError::InvalidToken {
+            line: lines.line,
+            token: fmt.to_string(),
+        });
+    }
+    let fmt: Vec<bool> = fmt.chars().rev().map(|c| c == '1').collect();
+    let has_edge_weights = fmt.first().copied().unwrap_or(false);
+    let has_vertex_weights = fmt.get(1).copied().unwrap_or(false);
+    let has_vertex_sizes = fmt.get(2).copied().unwrap_or(false);
+    let vertex_weights = match header_tokens.get(3) {
+        Some(ncon) if has_vertex_weights => parse_number(ncon, lines.line)?,
+        _ if has_vertex_weights => 1,
+        _ => 0,
+    };
+
+    let mut edges = vec![];
+    let mut weights = vec![];
+    let mut vertex = 0;
+    while vertex < vertex_count {
+        let content = match lines.next_line()? {
+            Some(content) if content.trim_start().starts_with('%') => continue,
+            Some(content) => content,
+            None => {
+                return Err(GraphParseError::MissingAdjacency {
+                    line: lines.line + 1,
+                    vertex,
+                })
+            }
+        };
+
+        let mut tokens = content.split_whitespace();
+        /* skip the vertex size and keep only the first weight */
+        for index in 0..(has_vertex_sizes as usize + vertex_weights) {
+            match tokens.next() {
+                Some(token) if index == has_vertex_sizes as usize => {
+                    weights.push(parse_number(token, lines.line)?)
+                }
+                Some(token) => {
+                    parse_number(token, lines.line)?;
+                }
+                None if index == has_vertex_sizes as usize => {
+                    return Err(GraphParseError::MissingWeight {
+                        line: lines.line,
+                        vertex,
+                    })
+                }
+                None => {}
+            }
+        }
+        while let Some(token) = tokens.next() {
+            let neighbor = parse_one_based_vertex(token, lines.line, vertex_count)?;
+            if has_edge_weights {
+                tokens.next();
+            }
+            if vertex < neighbor {
+                edges.push((vertex, neighbor));
+            }
+        }
+        vertex += 1;
+    }
+
+    Ok(EdgeList {
+        vertex_count,
+        edges,
+        weights: (vertex_weights > 0).then_some(weights),
+    })
+}
+
+pub fn read_edge_list_file<P: AsRef<Path>>(filename: P) -> Result<EdgeList, GraphParseError> {
+    let mut lines = GraphFileLines::open(filename)?;
+    let mut edges = vec![];
+    let mut vertex_count = 0;
+
+    while let Some(content) = lines.next_line()? {
+        let mut tokens = content.split_whitespace();
+        match (tokens.next(), tokens.next()) {
+            /* skip empty lines and comments */
+            (None, _) => continue,
+            (Some(token), _) if token.starts_with('#') || token.starts_with('%') => continue,
+            (Some(u), Some(v)) => {
+                let u = parse_number(u, lines.line)?;
+                let v = parse_number(v, lines.line)?;
+                /* the vertices are counted from 0, so the largest id cannot be a vertex */
+                let Some(count) = u.max(v).checked_add(1) else {
+                    return Err(GraphParseError::VertexOutOfRange {
+                        line: lines.line,
+                        token: u.max(v).to_string(),
+                        vertex_count: usize::MAX,
+                    });
+                };
+                vertex_count = vertex_count.max(lines.vertex_count(count, lines.line)?);
+                edges.push((u, v));
+            }
+            (Some(_), None) => return Err(GraphParseError::MissingVertex { line: lines.line }),
+        }
+    }
+
+    Ok(EdgeList {
+        vertex_count,
+        edges,
+        weights: None,
+    })
+}
+
+pub fn read_graph_file_into_quick_graph<P: AsRef<Path>>(
+    filename: P,
+) -> Result<QuickGraph, GraphParseError> {
+    Ok(QuickGraph::from(&read_graph_file_into_arc_list(filename)?))
+}
+
+pub fn read_graph_file_into_dcel_builder<P: AsRef<Path>>(
+    filename: P,
+) -> Result<DcelBuilder, GraphParseError> {
+    Ok(DcelBuilder::from(&read_graph_file_into_arc_list(filename)?))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Write;
+
+    use super::{
+        read_dimacs_file, read_edge_list_file, read_graph_file, read_graph_file_into_arc_list,
+        read_graph_file_into_dcel_builder, read_metis_file, read_pace_file, GraphFormat,
+        GraphParseError,
+    };
+    use crate::graph::quick_graph::QuickGraph;
+    use crate::graph::DcelBuilder;
+
+    fn write_graph_file(name: &str, content: &str) -> String {
+        let path = std::env::temp_dir().join(name);
+        let mut file = std::fs::File::create(&path).unwrap();
+        write!(file, "{content}").unwrap();
+        path.to_str().unwrap().to_string()
+    }
+
+    #[test]
+    fn arc_list() {
+        let path = write_graph_file("thm_ptas_arc_list.graph", "3\n2\n0 1\n1 0\n1 2\n2 1\n");
+        let arc_list = read_graph_file_into_arc_list(&path).unwrap();
+        assert_eq!(arc_list.vertex_count, 3);
+        assert_eq!(arc_list.edge_count(), 2);
+
+        let graph = QuickGraph::from(&arc_list);
+        assert_eq!(graph.edge_count, 2);
+        assert_eq!(graph.adjacency[1], Some(vec![0, 2]));
+
+        let dcel_builder = DcelBuilder::from(&arc_list);
+        assert_eq!(dcel_builder.vertex_count(), 3);
+        assert_eq!(dcel_builder.get_neighborhood(1), vec![0, 2]);
+        assert_eq!(arc_list.weights, None);
+    }
+
+    #[test]
+    fn weights() {
+        let path = write_graph_file(
+            "thm_ptas_weights.graph",
+            "3\n2\n0 1\n1 0\n1 2\n2 1\n\n5\n0\n7\n",
+        );
+        let arc_list = read_graph_file_into_arc_list(&path).unwrap();
+        assert_eq!(arc_list.weights, Some(vec![5, 0, 7]));
+
+        let path = write_graph_file("thm_ptas_short_weights.graph", "2\n1\n0 1\n1 0\n5\n");
+        match read_graph_file_into_arc_list(&path) {
+            Err(GraphParseError::MissingWeight { line: 6, vertex: 1 }) => {}
+            result => panic!("Unexpected result: {result:?}"),
+        }
+
+        let path = write_graph_file("thm_ptas_weights.col", "p edge 3 1\nn 3 4\ne 1 2\n");
+        assert_eq!(
+            read_dimacs_file(&path).unwrap().weights,
+            Some(vec![1, 1, 4])
+        );
+    }
+
+    #[test]
+    fn missing_header() {
+        let path = write_graph_file("thm_ptas_missing_header.graph", "3\n");
+        match read_graph_file_into_dcel_builder(&path) {
+            Err(GraphParseError::MissingHeader { line: 2, .. }) => {}
+            result => panic!("Unexpected result: {result:?}"),
+        }
+    }
+
+    #[test]
+    fn too_few_arcs() {
+        let path = write_graph_file("thm_ptas_too_few_arcs.graph", "2\n1\n0 1\n");
+        match read_graph_file_into_dcel_builder(&path) {
+            Err(GraphParseError::TooFewArcs {
+                line: 4,
+                expected: 2,
+                found: 1,
+            }) => {}
+            result => panic!("Unexpected result: {result:?}"),
+        }
+    }
+
+    #[test]
+    fn too_many_edges() {
+        let path = write_graph_file(
+            "thm_ptas_too_many_edges.graph",
+            "2\n9223372036854775808\n0 1\n",
+        );
+        match read_graph_file_into_dcel_builder(&path) {
+            Err(GraphParseError::TooManyEdges { line: 2, .. }) => {}
+            result => panic!("Unexpected result: {result:?}"),
+        }
+    }
+
+    #[test]
+    fn untrusted_counts() {
+        /* the edge counts of the headers are far too large to be preallocated */
+        let path = write_graph_file("thm_ptas_huge.graph", "2\n4611686018427387903\n0 1\n");
+        match read_graph_file_into_dcel_builder(&path) {
+            Err(GraphParseError::TooFewArcs {
+                line: 4, found: 1, ..
+            }) => {}
+            result => panic!("Unexpected result: {result:?}"),
+        }
+        let path = write_graph_file("thm_ptas_huge.col", "p edge 2 4611686018427387903\ne 1 2\n");
+        assert_eq!(read_dimacs_file(&path).unwrap().edges, vec![(0, 1)]);
+        let path = write_graph_file("thm_ptas_huge.metis", "2 4611686018427387903\n2\n1\n");
+        assert_eq!(read_metis_file(&path).unwrap().edges, vec![(0, 1)]);
+
+        let path = write_graph_file("thm_ptas_huge.txt", "0 18446744073709551615\n");
+        match read_edge_list_file(&path) {
+            Err(GraphParseError::VertexOutOfRange { line: 1, .. }) => {}
+            result => panic!("Unexpected result: {result:?}"),
+        }
+    }
+
+    #[test]
+    fn too_many_vertices() {
+        let path = write_graph_file("thm_ptas_many_vertices.graph", "100000000000000\n0\n");
+        match read_graph_file_into_dcel_builder(&path) {
+            Err(GraphParseError::TooManyVertices {
+                line: 1,
+                vertex_count: 100000000000000,
+                file_size: 18,
+            }) => {}
+            result => panic!("Unexpected result: {result:?}"),
+        }
+        let path = write_graph_file("thm_ptas_isolated.graph", "3\n0\n");
+        let builder = read_graph_file_into_dcel_builder(&path).unwrap();
+        assert_eq!(builder.vertex_count(), 3);
+    }
+
+    #[test]
+    fn too_many_dimacs_vertices() {
+        let path = write_graph_file("thm_ptas_many_vertices.col", "p edge 100000000000000 1\n");
+        match read_dimacs_file(&path) {
+            Err(GraphParseError::TooManyVertices { line: 1, .. }) => {}
+            result => panic!("Unexpected result: {result:?}"),
+        }
+        let path = write_graph_file("thm_ptas_many_vertices.gr", "p td 100000000000000 1\n1 2\n");
+        match read_pace_file(&path) {
+            Err(GraphParseError::TooManyVertices { line: 1, .. }) => {}
+            result => panic!("Unexpected result: {result:?}"),
+        }
+        let path = write_graph_file("thm_ptas_many_vertices.txt", "0 1\n0 1000000000000\n");
+        match read_edge_list_file(&path) {
+            Err(GraphParseError::TooManyVertices {
+                line: 2,
+                vertex_count: 1000000000001,
+                ..
+            }) => {}
+            result => panic!("Unexpected result: {result:?}"),
+        }
+    }
+
+    #[test]
+    fn invalid_token() {
+        let path = write_graph_file("thm_ptas_invalid_token.graph", "2\n1\n0 x\n1 0\n");
+        match read_graph_file_into_dcel_builder(&path) {
+            Err(GraphParseError::InvalidToken { line: 3, token }) => assert_eq!(token, "x"),
+            result => panic!("Unexpected result: {result:?}"),
+        }
+    }
+
+    #[test]
+    fn vertex_out_of_range() {
+        let path = write_graph_file("thm_ptas_out_of_range.graph", "2\n1\n0 1\n2 0\n");
+        match read_graph_file_into_dcel_builder(&path) {
+            Err(GraphParseError::VertexOutOfRange { line: 4, token, .. }) => assert_eq!(token, "2"),
+            result => panic!("Unexpected result: {result:?}"),
+        }
+    }
+
+    #[test]
+    fn dimacs() {
+        let path = write_graph_file(
+            "thm_ptas_dimacs.col",
+            "c a triangle\np edge 4 3\ne 1 2\ne 2 3\n\ne 3 1\n",
+        );
+        let edge_list = read_dimacs_file(&path).unwrap();
+        assert_eq!(edge_list.vertex_count, 4);
+        assert_eq!(edge_list.edges, vec![(0, 1), (1, 2), (2, 0)]);
+        assert_eq!(GraphFormat::from_path(&path), GraphFormat::Dimacs);
+
+        let path = write_graph_file("thm_ptas_dimacs_no_header.col", "e 1 2\n");
+        match read_dimacs_file(&path) {
+            Err(GraphParseError::MissingHeader { line: 1, .. }) => {}
+            result => panic!("Unexpected result: {result:?}"),
+        }
+
+        let path = write_graph_file("thm_ptas_dimacs_zero.col", "p edge 2 1\ne 0 1\n");
+        match read_dimacs_file(&path) {
+            Err(GraphParseError::VertexOutOfRange { line: 2, token, .. }) => assert_eq!(token, "0"),
+            result => panic!("Unexpected result: {result:?}"),
+        }
+    }
+
+    #[test]
+    fn metis() {
+        /* vertex 4 is isolated, every vertex has a weight and every edge has a weight */
+        let path = write_graph_file(
+            "thm_ptas_metis.metis",
+            "% comment\n4 2 11\n5 2 1\n1 1 1 3 1\n% comment\n7 2 1\n1\n",
+        );
+        let edge_list = read_metis_file(&path).unwrap();
+        assert_eq!(edge_list.vertex_count, 4);
+        assert_eq!(edge_list.edges, vec![(0, 1), (1, 2)]);
+        assert_eq!(edge_list.weights, Some(vec![5, 1, 7, 1]));
+
+        let path = write_graph_file("thm_ptas_metis_short.metis", "3 1\n2\n1\n");
+        match read_metis_file(&path) {
+            Err(GraphParseError::MissingAdjacency { line: 4, vertex: 2 }) => {}
+            result => panic!("Unexpected result: {result:?}"),
+        }
+    }
+
+    #[test]
+    fn pace_and_edge_list() {
+        let path = write_graph_file("thm_ptas_pace.gr", "c comment\np td 3 2\n1 2\n2 3\n");
+        let edge_list = read_pace_file(&path).unwrap();
+        assert_eq!(edge_list.vertex_count, 3);
+        assert_eq!(edge_list.edges, vec![(0, 1), (1, 2)]);
+
+        let path = write_graph_file("thm_ptas_edges.txt", "# comment\n0 1\n1 2 0.5\n");
+        let edge_list = read_edge_list_file(&path).unwrap();
+        assert_eq!(edge_list.vertex_count, 3);
+        assert_eq!(edge_list.edges, vec![(0, 1), (1, 2)]);
+    }
+
+    #[test]
+    fn embeds_edge_formats() {
+        let path = write_graph_file("thm_ptas_square.txt", "0 1\n2 3\n1 2\n3 0\n0 2\n");
+        let arc_list = read_graph_file(&path, GraphFormat::EdgeList).unwrap();
+        assert_eq!(arc_list.vertex_count, 4);
+        assert_eq!(arc_list.edge_count(), 5);
+
+        let k5: String = (1..=5)
+            .flat_map(|u| (u + 1..=5).map(move |v| format!("e {u} {v}\n")))
+            .collect();
+        let path = write_graph_file("thm_ptas_k5.col", &format!("p edge 5 10\n{k5}"));
+        match read_graph_file(&path, GraphFormat::Dimacs) {
+            Err(GraphParseError::NotPlanar(error)) => {
+                assert_eq!(error.witness.map(|w| w.len()), Some(10))
+            }
+            result => panic!("Unexpected result: {result:?}"),
+        }
+    }
+
+    #[test]
+    fn isolated_vertices() {
+        let path = write_graph_file("thm_ptas_isolated.col", "p edge 3 1\ne 1 2\n");
+        let arc_list = read_graph_file(&path, GraphFormat::Dimacs).unwrap();
+        assert_eq!(DcelBuilder::from(&arc_list).vertex_count(), 3);
+    }
+}
@@ -0,0 +1,132 @@
+use std::fs::File;
+use std::io::{self, BufWriter, Write};
+use std::path::Path;
+
+use super::graph_file_reader::{ArcList, EdgeList, GraphFormat};
+
+/// Writes the graph in the given format.
+/// Only [GraphFormat::Embedding] and [GraphFormat::Metis] keep the order of the arcs around each
+/// vertex, all other formats only contain the edges.
+/// Vertex weights are written to embedding, DIMACS and METIS files, the other formats drop them.
+pub fn write_graph_file<P: AsRef<Path>>(
+    filename: P,
+    format: GraphFormat,
+    arc_list: &ArcList,
+) -> io::Result<()> {
+    let mut writer = BufWriter::new(File::create(filename)?);
+    let vertex_count = arc_list.vertex_count;
+    let edges = EdgeList::from(arc_list).edges;
+    let edge_count = edges.len();
+
+    match format {
+        GraphFormat::Embedding => {
+            writeln!(writer, "{vertex_count}")?;
+            writeln!(writer, "{}", arc_list.edge_count())?;
+            for (u, v) in &arc_list.arcs {
+                writeln!(writer, "{u} {v}")?;
+            }
+            for weight in arc_list.weights.iter().flatten() {
+                writeln!(writer, "{weight}")?;
+            }
+        }
+        GraphFormat::Dimacs => {
+            writeln!(writer, "p edge {vertex_count} {edge_count}")?;
+            for (v, weight) in arc_list.weights.iter().flatten().enumerate() {
+                writeln!(writer, "n {} {weight}", v + 1)?;
+            }
+            for (u, v) in edges {
+                writeln!(writer, "e {} {}", u + 1, v + 1)?;
+            }
+        }
+        GraphFormat::Metis => {
+            let mut adjacency = vec![vec![]; vertex_count];
+            if let Some(weights) = &arc_list.weights {
+                for (v, weight) in weights.iter().enumerate() {
+                    adjacency[v].push(weight.to_string());
+                }
+            }
+            for &(u, v) in &arc_list.arcs {
+                adjacency[u].push((v + 1).to_string());
+            }
+            match arc_list.weights {
+                Some(_) => writeln!(writer, "{vertex_count} {edge_count} 10")?,
+                None => writeln!(writer, "{vertex_count} {edge_count}")?,
+            }
+            for neighbors in adjacency {
+                writeln!(writer, "{}", neighbors.join(" "))?;
+            }
+        }
+        GraphFormat::Pace => {
+            writeln!(writer, "p td {vertex_count} {edge_count}")?;
+            for (u, v) in edges {
+                writeln!(writer, "{} {}", u + 1, v + 1)?;
+            }
+        }
+        GraphFormat::EdgeList => {
+            for (u, v) in edges {
+                writeln!(writer, "{u} {v}")?;
+            }
+        }
+    }
+
+    writer.flush()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::write_graph_file;
+    use crate::graph::graph_file_reader::{
+        read_graph_file, read_graph_file_into_arc_list, EdgeList, GraphFormat,
+    };
+
+    fn sorted_edges(edge_list: EdgeList) -> Vec<(usize, usize)> {
+        let mut edges = edge_list.edges;
+        edges.sort();
+        edges
+    }
+
+    #[test]
+    fn round_trip() {
+        let arc_list = read_graph_file_into_arc_list("data/bsp3.graph").unwrap();
+        for format in [
+            GraphFormat::Embedding,
+            GraphFormat::Dimacs,
+            GraphFormat::Metis,
+            GraphFormat::Pace,
+            GraphFormat::EdgeList,
+        ] {
+            let path = std::env::temp_dir().join(format!("thm_ptas_round_trip_{format:?}"));
+            let path = path.to_str().unwrap();
+            write_graph_file(path, format, &arc_list).unwrap();
+
+            let read = read_graph_file(path, format).unwrap();
+            assert_eq!(read.vertex_count, arc_list.vertex_count);
+            assert_eq!(
+                sorted_edges(EdgeList::from(&read)),
+                sorted_edges(EdgeList::from(&arc_list))
+            );
+            if format == GraphFormat::Embedding {
+                assert_eq!(read.arcs, arc_list.arcs);
+            }
+        }
+    }
+
+    #[test]
+    fn round_trip_weights() {
+        let mut arc_list = read_graph_file_into_arc_list("data/tri.graph").unwrap();
+        arc_list.weights = Some(vec![3, 1, 4, 1]);
+        for format in [
+            GraphFormat::Embedding,
+            GraphFormat::Dimacs,
+            GraphFormat::Metis,
+        ] {
+            let path = std::env::temp_dir().join(format!("thm_ptas_round_trip_weights_{format:?}"));
+            let path = path.to_str().unwrap();
+            write_graph_file(path, format, &arc_list).unwrap();
+            assert_eq!(
+                read_graph_file(path, format).unwrap().weights,
+                arc_list.weights
+            );
+        }
+    }
+}
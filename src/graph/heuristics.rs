@@ -0,0 +1,235 @@
+/* Heuristics for the maximum (weight) independent set that work on any graph, but give no
+ * guarantee. They are used by the heuristic scheme and to improve the sets of the other schemes. */
+use std::cmp::Ordering;
+use std::collections::{BinaryHeap, VecDeque};
+
+use super::dcel::vertex::VertexId;
+use super::mis_finder::Weight;
+use super::quick_graph::QuickGraph;
+
+/* a vertex that may be added by the greedy algorithm, the best has the largest
+ * weight / (degree + 1) */
+#[derive(PartialEq, Eq)]
+struct Candidate {
+    weight: Weight,
+    degree: usize,
+    vertex: VertexId,
+}
+
+impl Ord for Candidate {
+    fn cmp(&self, other: &Self) -> Ordering {
+        let this = self.weight as u128 * (other.degree as u128 + 1);
+        let that = other.weight as u128 * (self.degree as u128 + 1);
+        /* ties are broken by the smaller vertex, so the result is deterministic */
+        this.cmp(&that).then(other.vertex.cmp(&self.vertex))
+    }
+}
+
+impl PartialOrd for Candidate {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+/// Finds an independent set by repeatedly adding the vertex with the smallest degree in the
+/// remaining graph and removing its neighbors.
+/// For weighted graphs, the vertex with the largest `weight / (degree + 1)` is added instead,
+/// which is the same for equal weights. Removed vertices of the graph are ignored.
+pub fn find_greedy_mis(graph: &QuickGraph, weights: &[Weight]) -> Vec<VertexId> {
+    let neighbors = |v: VertexId| graph.adjacency[v].iter().flatten().copied();
+    let mut removed: Vec<bool> = graph.adjacency.iter().map(Option::is_none).collect();
+    let mut degree: Vec<usize> = (0..graph.adjacency.len())
+        .map(|v| neighbors(v).count())
+        .collect();
+
+    let mut queue: BinaryHeap<Candidate> = (0..graph.adjacency.len())
+        .filter(|&v| !removed[v])
+        .map(|v| Candidate {
+            weight: weights[v],
+            degree: degree[v],
+            vertex: v,
+        })
+        .collect();
+
+    let mut set = vec![];
+    while let Some(Candidate {
+        vertex, degree: d, ..
+    }) = queue.pop()
+    {
+        /* the degrees only decrease, outdated candidates are skipped */
+        if removed[vertex] || degree[vertex] != d {
+            continue;
+        }
+        set.push(vertex);
+        removed[vertex] = true;
+        for neighbor in neighbors(vertex)
+            .filter(|&n| !removed[n])
+            .collect::<Vec<_>>()
+        {
+            removed[neighbor] = true;
+            for second in neighbors(neighbor).filter(|&n| !removed[n]) {
+                degree[second] -= 1;
+                queue.push(Candidate {
+                    weight: weights[second],
+                    degree: degree[second],
+                    vertex: second,
+                });
+            }
+        }
+    }
+    set
+}
+
+/// Improves an independent set with the local search of Andrade, Resende and Werneck (ARW)
+/// until no improvement is left.
+///
+/// A vertex is `t`-tight if `t` of its neighbors are in the set. Free (0-tight) vertices are
+/// added, and a vertex `x` of the set is swapped for an independent set of its 1-tight neighbors
+/// that weighs more than `x`. For equal weights these are the 2-improvements of ARW, which
+/// replace one vertex by two. Every improvement increases the weight, so the search ends.
+pub fn improve_mis(graph: &QuickGraph, weights: &[Weight], set: &[VertexId]) -> Vec<VertexId> {
+    let neighbors = |v: VertexId| graph.adjacency[v].iter().flatten().copied();
+    let n = graph.adjacency.len();
+    let mut in_set = vec![false; n];
+    let mut tightness = vec![0usize; n];
+    let insert = |v: VertexId, in_set: &mut Vec<bool>, tightness: &mut Vec<usize>| {
+        in_set[v] = true;
+        neighbors(v).for_each(|u| tightness[u] += 1);
+    };
+    let remove = |v: VertexId, in_set: &mut Vec<bool>, tightness: &mut Vec<usize>| {
+        in_set[v] = false;
+        neighbors(v).for_each(|u| tightness[u] -= 1);
+    };
+    for &v in set {
+        insert(v, &mut in_set, &mut tightness);
+    }
+
+    /* vertices of the set whose neighborhood changed and that may be swapped */
+    let mut queue: VecDeque<VertexId> = set.iter().copied().collect();
+    let mut queued = in_set.clone();
+    let enqueue = |v: VertexId, queue: &mut VecDeque<VertexId>, queued: &mut Vec<bool>| {
+        if !queued[v] {
+            queued[v] = true;
+            queue.push_back(v);
+        }
+    };
+
+    for v in (0..n).filter(|&v| graph.adjacency[v].is_some()) {
+        if !in_set[v] && tightness[v] == 0 {
+            insert(v, &mut in_set, &mut tightness);
+            enqueue(v, &mut queue, &mut queued);
+        }
+    }
+
+    while let Some(x) = queue.pop_front() {
+        queued[x] = false;
+        if !in_set[x] {
+            continue;
+        }
+
+        let mut candidates: Vec<VertexId> = neighbors(x).filter(|&u| tightness[u] == 1).collect();
+        candidates.sort_unstable_by(|&u, &v| weights[v].cmp(&weights[u]).then(u.cmp(&v)));
+        candidates.dedup();
+
+        /* every candidate starts a greedy independent set of the other candidates */
+        let mut best: Option<(Weight, Vec<VertexId>)> = None;
+        for (i, &first) in candidates.iter().enumerate() {
+            let mut swap = vec![first];
+            for &u in candidates.iter().skip(i + 1) {
+                if swap.iter().all(|&v| !graph.are_adjacent(u, v)) {
+                    swap.push(u);
+                }
+            }
+            let weight: Weight = swap.iter().map(|&v| weights[v]).sum();
+            if best.as_ref().is_none_or(|(best, _)| weight > *best) {
+                best = Some((weight, swap));
+            }
+        }
+
+        let Some((weight, swap)) = best else {
+            continue;
+        };
+        if weight <= weights[x] {
+            continue;
+        }
+        remove(x, &mut in_set, &mut tightness);
+        for &v in &swap {
+            insert(v, &mut in_set, &mut tightness);
+        }
+
+        /* the neighbors of x may be free now, and the set changed around the new vertices */
+        for u in neighbors(x) {
+            if !in_set[u] && tightness[u] == 0 {
+                insert(u, &mut in_set, &mut tightness);
+                enqueue(u, &mut queue, &mut queued);
+            }
+        }
+        for &v in &swap {
+            enqueue(v, &mut queue, &mut queued);
+            for u in neighbors(v) {
+                neighbors(u)
+                    .filter(|&w| in_set[w])
+                    .for_each(|w| enqueue(w, &mut queue, &mut queued));
+            }
+        }
+    }
+
+    (0..n).filter(|&v| in_set[v]).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{find_greedy_mis, improve_mis};
+    use crate::graph::mis_finder::{find_connected_vertices, find_weighted_mis_exhaustive};
+    use crate::graph::quick_graph::QuickGraph;
+    use crate::read_graph_file_into_dcel_builder;
+    use std::collections::HashSet;
+
+    #[test]
+    fn heuristics() {
+        for file in ["tree", "tri", "simple", "bsp3"] {
+            let mut builder =
+                read_graph_file_into_dcel_builder(&format!("data/{file}.graph")).unwrap();
+            let graph = QuickGraph::from(&builder);
+            let adjacency = builder.build().adjacency_matrix();
+            for weights in [
+                vec![1; adjacency.len()],
+                (0..adjacency.len()).map(|v| v * 7 % 5 + 1).collect(),
+            ] {
+                let weight = |set: &[usize]| set.iter().map(|&v| weights[v]).sum::<usize>();
+                let greedy = find_greedy_mis(&graph, &weights);
+                let improved = improve_mis(&graph, &weights, &greedy);
+                for set in [&greedy, &improved] {
+                    let unique: HashSet<usize> = set.iter().copied().collect();
+                    assert_eq!(unique.len(), set.len());
+                    assert!(find_connected_vertices(&unique, &adjacency).is_empty());
+                }
+                assert!(weight(&improved) >= weight(&greedy));
+
+                /* the empty set is improved by adding free vertices */
+                let from_empty = improve_mis(&graph, &weights, &[]);
+                assert!(!from_empty.is_empty());
+
+                if adjacency.len() <= 16 {
+                    let (_, optimum) = find_weighted_mis_exhaustive(&adjacency, &weights).unwrap();
+                    assert!(weight(&improved) <= optimum);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn two_improvement() {
+        /* the center of a star is replaced by its leaves */
+        let mut graph = QuickGraph::new(4);
+        for leaf in 1..4 {
+            graph.adjacency[0].as_mut().unwrap().push(leaf);
+            graph.adjacency[leaf].as_mut().unwrap().push(0);
+        }
+        graph.edge_count = 3;
+        let mut improved = improve_mis(&graph, &[1; 4], &[0]);
+        improved.sort_unstable();
+        assert_eq!(improved, vec![1, 2, 3]);
+        assert_eq!(improve_mis(&graph, &[5, 1, 1, 1], &[0]), vec![0]);
+    }
+}
@@ -1,63 +1,37 @@
-use std::io::Write;
-use std::{collections::HashSet, fs::File};
+use std::collections::HashSet;
 
+use arboretum_td::tree_decomposition::Bag;
 use bit_set::BitSet;
 use fxhash::FxHashSet;
+use rayon::prelude::*;
 
-use crate::graph::{
-    dyn_table::{dt_fast::NtdAndFastTable, dt_normal::NtdAndNormalTable},
-    iterators::subset::SubsetIter,
-};
+use crate::graph::iterators::subset::SubsetIter;
 use crate::log_if_enabled;
 
 use super::{
-    dyn_table::{
-        dt_fast::FastDynTable,
-        dt_normal::{DynTableValue, DynTableValueItem, NormalDynTable},
-    },
-    iterators::{post_order::PostOrderIter, subset::SubBitSetIter},
+    dyn_table::dt_dense::{can_allocate_table, insert_bit, remove_bit, DenseBag},
+    iterators::subset::SubBitSetIter,
     nice_tree_decomp::NiceTreeDecomposition,
-    node_relations::NodeRelations,
+    ntd_problem::{
+        solve_ntd_problem, solve_ntd_tables, BagKind, BagTable, NtdError, NtdProblem, NtdTables,
+        TableEntry,
+    },
 };
 
 static MIS_LOG_PATH: &str = "logs/mis_log.txt";
-static CONSTR_TABLE_LOG_PATH: &str = "logs/constr_table.txt";
-static TABLE_LOG_PATH: &str = "logs/dyn_table.txt";
+
+/// The weight of a vertex. Without weights, every vertex has weight 1, so the weight of a set is
+/// its size.
+pub type Weight = usize;
 
 /// Represents a maximum independent set size that can either be a positive integer or negative
 /// infinity. In order to avoid arithmetic overflows, the addition and subtraction operators are
 /// overloaded and negative infinity "consumes" valid values.
+/// For weighted graphs, the size is the total weight of the set.
 #[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Clone, Copy)]
 pub enum MisSize {
     Invalid,
-    Valid(usize),
-}
-
-/// Represents a dynamic table for the maximum independent set algorithm.
-/// The functions for the algorithm are [find_mis] and [find_mis_fast].
-pub trait DynTable<'a, Set>
-where
-    Set: Default + Clone + Eq + std::fmt::Debug,
-{
-    /// Returns the set index and the maximum independent set size for a given bag ID and subset.
-    fn get(&self, bag_id: usize, subset: &Set) -> (usize, MisSize);
-
-    // TODO: @cleanup This function may only be needed for debugging purposes.
-    /// Returns the subset and its maximum independent set size for a given abg ID and the subset
-    /// index.
-    fn get_by_index(&self, bag_id: usize, subset_index: usize) -> (&Set, MisSize);
-
-    /// Returns a list of set indices and corresponding maximum independent set sizes for the root
-    /// bag. It is used to start the reconstruction of the maximum independent set, see
-    /// [reconstruct_mis].
-    fn get_max_root_set_indices(&self, root_id: usize) -> Vec<(usize, MisSize)>;
-
-    /// Inserts a bag ID, a subset and the calculated maximum independent set size into the table.
-    fn put<'b: 'a>(&'a mut self, bag_id: usize, subset: Set, size: MisSize);
-
-    /// Adds the vertices from the subset described by the given bag ID and subset index to the
-    /// maximum independent set [`mis`]. This function is used in the reconstruction algorithm.
-    fn add_to_mis(&self, bag_id: usize, subset_index: usize, mis: &mut HashSet<usize>);
+    Valid(Weight),
 }
 
 impl std::ops::Add for MisSize {
@@ -103,6 +77,18 @@ impl std::fmt::Display for MisSize {
 pub enum FindMisError {
     InvalidNiceTD,
     NoMisFound,
+    /// A bag has more vertices than a [DenseBag] can handle, or its table does not fit into
+    /// memory, see [can_allocate_table].
+    BagTooLarge(usize),
+}
+
+impl From<NtdError> for FindMisError {
+    fn from(error: NtdError) -> Self {
+        match error {
+            NtdError::InvalidNiceTD => FindMisError::InvalidNiceTD,
+            NtdError::NoSolution => FindMisError::NoMisFound,
+        }
+    }
 }
 
 impl std::error::Error for FindMisError {}
@@ -112,452 +98,402 @@ impl std::fmt::Display for FindMisError {
         match self {
             FindMisError::InvalidNiceTD => write!(f, "Invalid nice tree decomposition!"),
             FindMisError::NoMisFound => write!(f, "Could not find an maximum independent set!"),
+            FindMisError::BagTooLarge(size) => write!(
+                f,
+                "Bag with {size} vertices is too large, its table cannot be allocated!"
+            ),
         }
     }
 }
 
-/// Represents a 2D matrix that stores information about the construction of dynamic table values.
-type ConstructionTable = Vec<Vec<(Option<usize>, Option<usize>)>>;
-
-/// Checks whether `v` is independent from all vertices in `set` or not.
-fn is_independent(adjaceny_matrix: &Vec<Vec<bool>>, v: usize, set: &FxHashSet<usize>) -> bool {
-    set.iter().all(|u| !adjaceny_matrix[v][*u])
+/// Returns the total weight of the vertices in `set`.
+fn set_weight(weights: &[Weight], set: impl IntoIterator<Item = usize>) -> MisSize {
+    MisSize::Valid(set.into_iter().map(|v| weights[v]).sum())
 }
 
-/// Reconstructs the maximum independent set from the dynamic table.
-/// It recursively traverses the construction table while building the maximum independent set.
-fn reconstruct_mis<Set>(
-    table: &dyn DynTable<Set>,
-    root_id: usize,
-    constr_table: &ConstructionTable,
-    node_relations: &NodeRelations,
-    adjaceny_matrix: &Vec<Vec<bool>>,
-) -> HashSet<usize>
-where
-    Set: Eq + std::fmt::Debug + Clone + Default,
-{
-    /// Recursively traverses the table while building the maximum independent set.
-    fn rec<Set>(
-        table: &dyn DynTable<Set>,
-        bag_id: usize,
-        set_index: usize,
-        constr_table: &ConstructionTable,
-        mut mis: HashSet<usize>, // We move the set every time.
-        node_relations: &NodeRelations,
-    ) -> HashSet<usize>
-    where
-        Set: Eq + std::fmt::Debug + Clone + Default,
-    {
-        table.add_to_mis(bag_id, set_index, &mut mis);
-
-        let (subset, size) = table.get_by_index(bag_id, set_index);
-        log_if_enabled!(
-            MIS_LOG_PATH,
-            "({set_index}): MIS at M[{bag_id}, {subset:?}] = {size}:\t{mis:?}"
-        );
-
-        let children = &node_relations.children[&bag_id];
-        log_if_enabled!(MIS_LOG_PATH, "{bag_id}'s children: {children:?}");
-
-        match children.len() {
-            // Leaf node: We're finished.
-            0 => {
-                log_if_enabled!(MIS_LOG_PATH, "{bag_id} is a leaf node => MIS = {mis:?}");
-                mis
-            }
-
-            // Check the child.
-            1 => {
-                let child_index = constr_table[bag_id][set_index].0.unwrap();
-                log_if_enabled!(
-                    MIS_LOG_PATH,
-                    "{bag_id}'s {set_index} {:?} was constructed by {}'s {} {:?}",
-                    table.get_by_index(bag_id, set_index),
-                    children[0],
-                    child_index,
-                    table.get_by_index(children[0], child_index),
-                );
-
-                rec(
-                    table,
-                    children[0],
-                    constr_table[bag_id][set_index].0.unwrap(),
-                    &constr_table,
-                    mis,
-                    &node_relations,
-                )
-            }
-
-            // Find the maximum from the left and right child.
-            2 => {
-                let left = constr_table[bag_id][set_index].0.unwrap();
-                let right = constr_table[bag_id][set_index].1.unwrap();
-                log_if_enabled!(
-                    MIS_LOG_PATH,
-                    "{bag_id}'s {set_index} {:?} was constructed by {}'s {} {:?} and {}'s {} {:?}",
-                    table.get_by_index(bag_id, set_index),
-                    children[0],
-                    constr_table[bag_id][set_index].0.unwrap(),
-                    table.get_by_index(children[0], left),
-                    children[1],
-                    constr_table[bag_id][set_index].1.unwrap(),
-                    table.get_by_index(children[1], right),
-                );
+/// The maximum (weight) independent set problem for [solve_ntd_problem].
+/// The state of a bag is the subset of its vertices that is in the independent set, its value is
+/// the weight of the largest independent set of the subtree that contains this subset.
+pub struct IndependentSet<'a> {
+    adjaceny_matrix: &'a Vec<Vec<bool>>,
+    weights: &'a [Weight],
+}
 
-                let left_mis = mis.clone();
-                let right_mis = mis.clone();
+impl<'a> IndependentSet<'a> {
+    pub fn new(adjaceny_matrix: &'a Vec<Vec<bool>>, weights: &'a [Weight]) -> Self {
+        IndependentSet {
+            adjaceny_matrix,
+            weights,
+        }
+    }
 
-                let left = rec(
-                    table,
-                    children[0],
-                    constr_table[bag_id][set_index].0.unwrap(),
-                    &constr_table,
-                    left_mis,
-                    &node_relations,
-                );
+    /// Checks whether `v` is independent from all vertices in `set` or not.
+    fn is_independent(&self, v: usize, set: &BitSet) -> bool {
+        set.iter().all(|u| !self.adjaceny_matrix[u][v])
+    }
+}
 
-                let right = rec(
-                    table,
-                    children[1],
-                    constr_table[bag_id][set_index].1.unwrap(),
-                    &constr_table,
-                    right_mis,
-                    &node_relations,
-                );
+impl NtdProblem for IndependentSet<'_> {
+    type State = BitSet;
+    type Value = MisSize;
+
+    fn leaf(&self, bag: &Bag) -> Vec<TableEntry<BitSet, MisSize>> {
+        SubBitSetIter::new(&bag.vertex_set)
+            .map(|subset| {
+                let size = match subset.iter().all(|v| self.is_independent(v, &subset)) {
+                    true => set_weight(self.weights, subset.iter()),
+                    false => MisSize::Invalid,
+                };
+                TableEntry::new(subset, size, (None, None))
+            })
+            .collect()
+    }
 
-                mis = HashSet::from_iter(left.union(&right).into_iter().copied());
-                mis
-            }
+    fn introduce(
+        &self,
+        bag: &Bag,
+        v: usize,
+        child: &BagTable<BitSet, MisSize>,
+    ) -> Vec<TableEntry<BitSet, MisSize>> {
+        SubBitSetIter::new(&bag.vertex_set)
+            .map(|subset| {
+                if !subset.contains(v) {
+                    let (i, size) = child.get(&subset);
+                    log_if_enabled!(MIS_LOG_PATH,
+                        "{v} notin {subset:?} => M[{}, {subset:?}] = M[C, {subset:?}] = {size}",
+                        bag.id
+                    );
+                    TableEntry::new(subset, size, (Some(i), None))
+                } else if self.is_independent(v, &subset) {
+                    let mut clone = subset.clone();
+                    clone.remove(v);
+                    let (i, size) = child.get(&clone);
+                    log_if_enabled!(MIS_LOG_PATH,
+                        "{v} in {subset:?} => M[{}, {subset:?}] = M[C, {clone:?}] + w({v}) = {size} + {}",
+                        bag.id, self.weights[v]
+                    );
+                    let size = size + MisSize::Valid(self.weights[v]);
+                    TableEntry::new(subset, size, (Some(i), None))
+                } else {
+                    log_if_enabled!(
+                        MIS_LOG_PATH,
+                        "{subset:?} is not independent => M[{}, S] = -infinity",
+                        bag.id
+                    );
+                    TableEntry::new(subset, MisSize::Invalid, (None, None))
+                }
+            })
+            .collect()
+    }
 
-            _ => panic!("Unreachable"),
-        }
+    fn forget(
+        &self,
+        bag: &Bag,
+        v: usize,
+        child: &BagTable<BitSet, MisSize>,
+    ) -> Vec<TableEntry<BitSet, MisSize>> {
+        SubBitSetIter::new(&bag.vertex_set)
+            .map(|subset| {
+                let mut clone = subset.clone();
+                clone.insert(v);
+
+                let with = child.get(&clone);
+                let without = child.get(&subset);
+                let (i, size) = std::cmp::max_by(with, without, |w, wo| w.1.cmp(&wo.1));
+                TableEntry::new(subset, size, (Some(i), None))
+            })
+            .collect()
     }
 
-    // We find all subsets with the largest number in the root entry.
-    let root_sets = table.get_max_root_set_indices(root_id);
-    log_if_enabled!(
-        MIS_LOG_PATH,
-        "MIS size according to table: {}",
-        root_sets[0].1
-    );
-
-    // We try to reconstruction the independent sets until one is found.
-    for (index, size) in root_sets.into_iter() {
-        let mut result = HashSet::new();
-        result = rec(
-            table,
-            root_id,
-            index,
-            &constr_table,
-            result,
-            &node_relations,
-        );
+    fn join(
+        &self,
+        bag: &Bag,
+        left: &BagTable<BitSet, MisSize>,
+        right: &BagTable<BitSet, MisSize>,
+    ) -> Vec<TableEntry<BitSet, MisSize>> {
+        // forall subsets of bag: M[bag, subset] = M[lc, subset] + M[rc, subset] - w(subset)
+        SubBitSetIter::new(&bag.vertex_set)
+            .map(|subset| {
+                let (i, left_size) = left.get(&subset);
+                let (j, right_size) = right.get(&subset);
+                let len = set_weight(self.weights, subset.iter());
+                log_if_enabled!(MIS_LOG_PATH, "M[{}, {subset:?}] = M[L, S] + M[R, S] - w(S) = {left_size} + {right_size} - {len}", bag.id);
+                TableEntry::new(subset, left_size + right_size - len, (Some(i), Some(j)))
+            })
+            .collect()
+    }
 
-        let connectes_vertices = find_connected_vertices(&result, &adjaceny_matrix);
-        if connectes_vertices.len() == 0 {
-            return result; // Return the found MIS.
-        }
+    fn best_root_entry(&self, _bag: &Bag, table: &BagTable<BitSet, MisSize>) -> Option<usize> {
+        table
+            .entries()
+            .iter()
+            .enumerate()
+            .filter(|(_, entry)| entry.value != MisSize::Invalid)
+            .max_by_key(|(_, entry)| entry.value)
+            .map(|(i, _)| i)
     }
 
-    HashSet::new() // No MIS found.
+    fn reconstruct(&self, _bag: &Bag, state: &BitSet, solution: &mut HashSet<usize>) {
+        solution.extend(state.iter());
+    }
 }
 
 /// Finds the maximum independent set as described in the
 /// [wiki](https://github.com/manuEbg/thm-ptas/wiki/Maximum-Independent-Set-with-Dynamic-Programming-on-Nice-Tree-Decompositions.)
 /// for this project.
 /// The nice tree decomposition [`ntd`] is traversed in post order (left child, right child, parent) and
-/// the independence is checked by the [`adjaceny_matrix`].
-/// This implementation uses the [NormalDynTable].
+/// the independence is checked by the `adjaceny_matrix`, see [IndependentSet].
 pub fn find_mis(
     adjaceny_matrix: &Vec<Vec<bool>>,
     ntd: &NiceTreeDecomposition,
 ) -> Result<(HashSet<usize>, usize), FindMisError> {
-    let mut table = NormalDynTable::default();
-
-    // TODO: @speed Don't use dynamic vectors, instead compute the maximum size required with
-    // `max_bag_size`? This should be at max something like the length of the spanning tree.
-    // Idea: The i-th set was constructed by the j-th child set.
-    let mut constr_table: ConstructionTable = vec![Vec::new(); ntd.td.bags().len()];
-
-    for bag in PostOrderIter::new(&ntd.td) {
-        let children = &ntd.relations.children[&bag.id];
-        let mut entry = DynTableValue::default();
-
-        match children.len() {
-            0 => {
-                // Leaf node.
-                entry.add(DynTableValueItem::new(
-                    FxHashSet::from_iter(Vec::new().into_iter()),
-                    MisSize::Valid(0),
-                ));
-                entry.add(DynTableValueItem::new(
-                    FxHashSet::from_iter(bag.vertex_set.iter().copied()),
-                    MisSize::Valid(1),
-                ));
-            }
-
-            1 => {
-                let child = &ntd.td.bags[children[0]];
-                let child_entry = table.0.get(&child.id).unwrap();
-                if let Some(&v) = bag.vertex_set.difference(&child.vertex_set).nth(0) {
-                    // Introduce node.
-                    for subset in SubsetIter::new(&bag.vertex_set) {
-                        if !subset.contains(&v) {
-                            let (i, size) = table.get(child.id, &subset);
-                            log_if_enabled!(MIS_LOG_PATH,
-                                "{v} notin {subset:?} => M[{}, {subset:?}] = M[{}, {subset:?}] = {size}",
-                                bag.id, child.id
-                            );
-                            entry.sets.push(DynTableValueItem::new(subset, size));
-                            constr_table[bag.id].push((Some(i), None)); // Reconstruction.
-                        } else if is_independent(&adjaceny_matrix, v, &subset) {
-                            // @speed This clone could be expensive.
-                            let mut clone = subset.clone();
-                            clone.remove(&v);
-                            let (i, size) = table.get(child.id, &clone);
-                            log_if_enabled!(MIS_LOG_PATH,
-                                "{v} in {subset:?} => M[{}, {subset:?}] = M[{}, {clone:?}] + 1 = {size} + 1",
-                                bag.id, child.id
-                            );
-                            entry
-                                .sets
-                                .push(DynTableValueItem::new(subset, size + MisSize::Valid(1)));
-                            constr_table[bag.id].push((Some(i), None)); // Reconstruction.
-                        } else {
-                            log_if_enabled!(
-                                MIS_LOG_PATH,
-                                "{subset:?} is not independent => M[{}, S] = -infinity",
-                                bag.id
-                            );
-                            entry
-                                .sets
-                                .push(DynTableValueItem::new(subset, MisSize::Invalid));
-                            constr_table[bag.id].push((None, None)); // Reconstruction.
-                        }
-                    }
-                } else if let Some(&v) = child.vertex_set.difference(&bag.vertex_set).nth(0) {
-                    // Forget node.
-                    // forall subsets of bag: M[bag, subset] = max { ... }.
-
-                    log_if_enabled!(
-                        MIS_LOG_PATH,
-                        "Forget: B{} -> B{} = {:?} -> {:?}",
-                        &child.id,
-                        &bag.id,
-                        &child.vertex_set,
-                        &bag.vertex_set
-                    );
-
-                    for subset in SubsetIter::new(&bag.vertex_set) {
-                        // @speed This clone could be expensive.
-                        let mut clone = subset.clone();
-                        clone.insert(v);
-
-                        let without = table.get(child.id, &subset);
-                        let with = table.get(child.id, &clone);
-
-                        let (i, value) = std::cmp::max_by(with, without, |w, wo| w.1.cmp(&wo.1));
-
-                        entry.sets.push(DynTableValueItem::new(subset, value));
-                        constr_table[bag.id].push((Some(i), None)); // Reconstruction.
-                    }
-                } else if child.vertex_set == bag.vertex_set {
-                    // Some weird edge case. Is this a problem from an earlier phase or can we handle
-                    // it this way?
-                    for subset in SubsetIter::new(&bag.vertex_set) {
-                        let (child_set_index, size) = table.get(child.id, &subset);
-                        entry.sets.push(DynTableValueItem::new(subset, size));
-                        // table.put(bag.id, subset, size);
-                        constr_table[bag.id].push((Some(child_set_index), None));
-                    }
-                } else {
-                    panic!("Unreachable");
-                }
-            }
-
-            2 => {
-                // Join node.
-                // forall subsets of bag: M[bag, subset] = M[lc, subset] + M[rc, subset] - |subset|
+    find_weighted_mis(adjaceny_matrix, &vec![1; adjaceny_matrix.len()], ntd)
+}
 
-                let left_child = &ntd.td.bags[children[0]];
-                let right_child = &ntd.td.bags[children[1]];
+/// Same as [find_mis], but maximizes the total weight of the set instead of its size.
+/// Returns the set and its weight.
+pub fn find_weighted_mis(
+    adjaceny_matrix: &Vec<Vec<bool>>,
+    weights: &[Weight],
+    ntd: &NiceTreeDecomposition,
+) -> Result<(HashSet<usize>, Weight), FindMisError> {
+    let problem = IndependentSet::new(adjaceny_matrix, weights);
+    match solve_ntd_problem(&problem, ntd)? {
+        (mis, MisSize::Valid(weight)) => Ok((mis, weight)),
+        (_, MisSize::Invalid) => Err(FindMisError::NoMisFound),
+    }
+}
 
-                for subset in SubsetIter::new(&bag.vertex_set) {
-                    let (i, left_size) = table.get(left_child.id, &subset);
-                    let (j, right_size) = table.get(right_child.id, &subset);
-                    let len = MisSize::Valid(subset.len());
+/// Same as [find_mis], but uses the dense tables of [DenseIndependentSet] instead of the tables
+/// of [IndependentSet]. Introduce, forget and join bags only need bit operations on the masks of
+/// their subsets, which is much faster for large bags.
+pub fn find_mis_fast(
+    adjaceny_matrix: &[Vec<bool>],
+    ntd: &NiceTreeDecomposition,
+) -> Result<(HashSet<usize>, usize), FindMisError> {
+    find_weighted_mis_fast(adjaceny_matrix, &vec![1; adjaceny_matrix.len()], ntd, false)
+}
 
-                    log_if_enabled!(MIS_LOG_PATH, "M[{}, {subset:?}] = M[{}, S] + M[{}, S] - |S| = {left_size} + {right_size} - {len} = {}", bag.id, left_child.id, right_child.id, left_size + right_size - len);
-                    entry
-                        .sets
-                        .push(DynTableValueItem::new(subset, left_size + right_size - len));
-                    constr_table[bag.id].push((Some(i), Some(j))); // Reconstruction.
-                }
-            }
+/// Same as [find_weighted_mis], but uses dense tables, see [find_mis_fast].
+/// Fails if the table of a bag cannot be allocated, see [can_allocate_table].
+///
+/// With `parallel`, the subsets of large bags and independent subtrees of the nice tree
+/// decomposition are computed in parallel on the current rayon thread pool. The result is the
+/// same as without it.
+pub fn find_weighted_mis_fast(
+    adjaceny_matrix: &[Vec<bool>],
+    weights: &[Weight],
+    ntd: &NiceTreeDecomposition,
+    parallel: bool,
+) -> Result<(HashSet<usize>, Weight), FindMisError> {
+    find_mis_dense(adjaceny_matrix, weights, ntd, true, parallel)
+}
 
-            _ => assert!(false, "Unreachable"),
-        }
+/// Same as [find_weighted_mis_fast], but only computes the weight of the maximum independent set.
+/// Nothing is kept for the reconstruction, so only the tables of the bags whose parent was not
+/// computed yet are in memory.
+pub fn find_mis_weight_fast(
+    adjaceny_matrix: &[Vec<bool>],
+    weights: &[Weight],
+    ntd: &NiceTreeDecomposition,
+    parallel: bool,
+) -> Result<Weight, FindMisError> {
+    let (_, weight) = find_mis_dense(adjaceny_matrix, weights, ntd, false, parallel)?;
+    Ok(weight)
+}
 
-        table.0.insert(bag.id.clone(), entry);
+/* runs the dynamic program with dense tables, the table of a bag is freed as soon as its parent
+ * is computed */
+fn find_mis_dense(
+    adjaceny_matrix: &[Vec<bool>],
+    weights: &[Weight],
+    ntd: &NiceTreeDecomposition,
+    reconstruct: bool,
+    parallel: bool,
+) -> Result<(HashSet<usize>, Weight), FindMisError> {
+    let max_bag_size = ntd.td.bags.iter().map(|bag| bag.vertex_set.len()).max();
+    if let Some(size) = max_bag_size.filter(|&size| !can_allocate_table(size)) {
+        return Err(FindMisError::BagTooLarge(size));
     }
 
-    #[cfg(feature = "logging")]
-    {
-        log_if_enabled!(TABLE_LOG_PATH, "-------------\nNormal Table\n-------------");
-        log_if_enabled!(
-            TABLE_LOG_PATH,
-            "{}",
-            NtdAndNormalTable { ntd, table: &table }
-        );
-        dump_construction_table("Normal table", &constr_table, &table, &ntd.relations);
-    }
+    let problem = DenseIndependentSet::new(adjaceny_matrix, weights, parallel);
+    Ok(solve_ntd_tables(&problem, ntd, reconstruct, parallel)?)
+}
 
-    let result = reconstruct_mis(
-        &table,
-        ntd.td.root.unwrap(),
-        &constr_table,
-        &ntd.relations,
-        &adjaceny_matrix,
-    );
+/* bags with fewer subsets are not split between threads */
+const PARALLEL_MIN_SUBSETS: usize = 1 << 10;
+
+/* computes `f` for the masks of all subsets of a bag */
+fn map_subsets<T: Send>(
+    subset_count: usize,
+    parallel: bool,
+    f: impl Fn(u64) -> T + Send + Sync,
+) -> Vec<T> {
+    match parallel && subset_count >= PARALLEL_MIN_SUBSETS {
+        true => (0..subset_count)
+            .into_par_iter()
+            .with_min_len(PARALLEL_MIN_SUBSETS)
+            .map(|mask| f(mask as u64))
+            .collect(),
+        false => (0..subset_count as u64).map(f).collect(),
+    }
+}
 
-    Ok((result.clone(), result.len()))
+/// The maximum (weight) independent set problem with dense tables for [solve_ntd_tables].
+/// The table of a bag is a [DenseBag], the index of an entry is the mask of its subset.
+///
+/// The trace of a forget bag is the set of masks whose best extension contains the forgotten
+/// vertex. These are the only choices of the dynamic program, the subsets of all other children
+/// follow from the subset of their parent, so the other bags have no trace.
+pub struct DenseIndependentSet<'a> {
+    adjaceny_matrix: &'a [Vec<bool>],
+    weights: &'a [Weight],
+    /// The subsets of large bags are computed in parallel.
+    parallel: bool,
 }
 
-/// Checks whether `v` is independent from all vertices in `set` or not.
-fn is_independent_fast(adjaceny_matrix: &Vec<Vec<bool>>, v: usize, set: &BitSet) -> bool {
-    set.iter().all(|u| !adjaceny_matrix[u][v])
+impl<'a> DenseIndependentSet<'a> {
+    pub fn new(adjaceny_matrix: &'a [Vec<bool>], weights: &'a [Weight], parallel: bool) -> Self {
+        DenseIndependentSet {
+            adjaceny_matrix,
+            weights,
+            parallel,
+        }
+    }
 }
 
-/// Finds the maximum independent set as described in the
-/// [wiki](https://github.com/manuEbg/thm-ptas/wiki/Maximum-Independent-Set-with-Dynamic-Programming-on-Nice-Tree-Decompositions.)
-/// for this project.
-/// The nice tree decomposition [`ntd`] is traversed in post order (left child, right child, parent) and
-/// the independence is checked by the [`adjaceny_matrix`].
-/// This implementation uses the [FastDynTable].
-pub fn find_mis_fast(
-    adjaceny_matrix: &Vec<Vec<bool>>,
-    ntd: &NiceTreeDecomposition,
-) -> Result<(HashSet<usize>, usize), FindMisError> {
-    let mut table: FastDynTable = FastDynTable::new(usize::pow(2, adjaceny_matrix.len() as u32));
-
-    // TODO: @speed Don't use dynamic vectors, instead compute the maximum size required with
-    // `max_bag_size`? This should be at max something like the length of the spanning tree.
-    // Idea: The i-th set was constructed by the j-th child set.
-    let mut constr_table: ConstructionTable = vec![Vec::new(); ntd.td.bags().len()];
-
-    for bag in PostOrderIter::new(&ntd.td) {
-        let children = &ntd.relations.children[&bag.id];
-
-        match children.len() {
-            0 => {
-                table.put(bag.id, BitSet::new(), MisSize::Valid(0));
-                table.put(
-                    bag.id,
-                    BitSet::from_iter(bag.vertex_set.iter().copied()),
-                    MisSize::Valid(1),
-                );
+impl NtdTables for DenseIndependentSet<'_> {
+    type Table = DenseBag;
+    type Trace = Option<BitSet>;
+    type Value = Weight;
+
+    fn compute(
+        &self,
+        bag: &Bag,
+        kind: BagKind,
+        children: Vec<DenseBag>,
+    ) -> (DenseBag, Option<BitSet>) {
+        let (adjaceny_matrix, weights, parallel) =
+            (self.adjaceny_matrix, self.weights, self.parallel);
+        let mut dense = DenseBag::new(&bag.vertex_set);
+        let mut with_v = None;
+
+        dense.sizes = match (kind, children.as_slice()) {
+            (BagKind::Leaf, []) => {
+                let neighbors: Vec<u64> = dense
+                    .vertices
+                    .iter()
+                    .map(|&v| dense.neighbor_mask(v, adjaceny_matrix))
+                    .collect();
+                let subset_weights = dense.subset_weights(weights);
+                map_subsets(dense.subset_count(), parallel, |mask| {
+                    let independent = (0..neighbors.len())
+                        .all(|i| mask & 1 << i == 0 || mask & neighbors[i] == 0);
+                    match independent {
+                        true => MisSize::Valid(subset_weights[mask as usize]),
+                        false => MisSize::Invalid,
+                    }
+                })
             }
 
-            1 => {
-                let child = &ntd.td.bags[children[0]];
-
-                if let Some(&v) = bag.vertex_set.difference(&child.vertex_set).nth(0) {
-                    // Introduce node.
-                    for subset in SubBitSetIter::new(&bag.vertex_set) {
-                        let set_index = constr_table[bag.id].len();
-                        if !subset.contains(v) {
-                            let (child_set_index, size) = table.get(child.id, &subset);
-                            log_if_enabled!(MIS_LOG_PATH,
-                                "{v} notin {subset:?} => M[{}, {subset:?}] = M[{}, {subset:?}] = {size}",
-                                bag.id, child.id
-                            );
-                            table.put(bag.id, subset, size);
-                            constr_table[bag.id].push((Some(child_set_index), None));
-                        } else if is_independent_fast(&adjaceny_matrix, v, &subset) {
-                            let mut clone = subset.clone();
-                            clone.remove(v);
-                            let (child_set_index, size) = table.get(child.id, &clone);
-                            log_if_enabled!(MIS_LOG_PATH,
-                                "{v} in {subset:?} => M[{}, {subset:?}] = M[{}, {clone:?}] + 1 = {size} + 1",
-                                bag.id, child.id
-                            );
-                            table.put(bag.id, subset, size + MisSize::Valid(1));
-                            constr_table[bag.id].push((Some(child_set_index), None));
-                        } else {
-                            log_if_enabled!(
-                                MIS_LOG_PATH,
-                                "{subset:?} is not independent => M[{}, S] = -infinity",
-                                bag.id
-                            );
-                            table.put(bag.id, subset, MisSize::Invalid);
-                            constr_table[bag.id].push((None, None));
-                        }
+            (BagKind::Introduce(v), [child]) => {
+                // introduce: M[bag, S] = M[C, S \ {v}] + w(v) if v in S and S is independent
+                let p = dense.position(v);
+                let neighbors = dense.neighbor_mask(v, adjaceny_matrix);
+                map_subsets(dense.subset_count(), parallel, |mask| {
+                    let size = child.sizes[remove_bit(mask, p) as usize];
+                    if mask & 1 << p == 0 {
+                        size
+                    } else if mask & neighbors == 0 {
+                        size + MisSize::Valid(weights[v])
+                    } else {
+                        MisSize::Invalid
                     }
-                } else if let Some(&v) = child.vertex_set.difference(&bag.vertex_set).nth(0) {
-                    // Forget node.
-                    for subset in SubBitSetIter::new(&bag.vertex_set) {
-                        let set_index = constr_table[bag.id].len();
-                        let mut clone = subset.clone();
-                        clone.insert(v);
+                })
+            }
 
-                        let with = table.get(child.id, &clone);
-                        let without = table.get(child.id, &subset);
+            (BagKind::Forget(v), [child]) => {
+                // forget: M[bag, S] = max(M[C, S], M[C, S + {v}])
+                let p = child.position(v);
+                let (sizes, with): (Vec<MisSize>, Vec<bool>) =
+                    map_subsets(dense.subset_count(), parallel, |mask| {
+                        let without = insert_bit(mask, p) as usize;
+                        let with = without | 1 << p;
+                        match child.sizes[with] > child.sizes[without] {
+                            true => (child.sizes[with], true),
+                            false => (child.sizes[without], false),
+                        }
+                    })
+                    .into_iter()
+                    .unzip();
+                with_v = Some(
+                    with.into_iter()
+                        .enumerate()
+                        .filter(|(_, with)| *with)
+                        .map(|(mask, _)| mask)
+                        .collect(),
+                );
+                sizes
+            }
 
-                        let (child_set_index, size) =
-                            std::cmp::max_by(with, without, |w, wo| w.1.cmp(&wo.1));
+            (BagKind::Copy, [_]) => children.into_iter().next().unwrap().sizes,
 
-                        table.put(bag.id, subset, size);
-                        constr_table[bag.id].push((Some(child_set_index), None));
-                    }
-                } else if child.vertex_set == bag.vertex_set {
-                    // Some weird edge case. Is this a problem from an earlier phase or can we handle
-                    // it this way?
-                    for subset in SubBitSetIter::new(&bag.vertex_set) {
-                        let (child_set_index, size) = table.get(child.id, &subset);
-                        table.put(bag.id, subset, size);
-                        constr_table[bag.id].push((Some(child_set_index), None));
-                    }
-                } else {
-                    panic!("Unreachable");
-                }
+            (BagKind::Join, [left, right]) => {
+                // M[bag, S] = M[L, S] + M[R, S] - w(S), the children have the same vertices
+                let subset_weights = dense.subset_weights(weights);
+                map_subsets(dense.subset_count(), parallel, |mask| {
+                    let mask = mask as usize;
+                    left.sizes[mask] + right.sizes[mask] - MisSize::Valid(subset_weights[mask])
+                })
             }
 
-            2 => {
-                // Join node.
-                let left_child = &ntd.td.bags[children[0]];
-                let right_child = &ntd.td.bags[children[1]];
-
-                for subset in SubBitSetIter::new(&bag.vertex_set) {
-                    let set_index = constr_table[bag.id].len();
-                    let (i, left_size) = table.get(left_child.id, &subset);
-                    let (j, right_size) = table.get(right_child.id, &subset);
-                    let len = MisSize::Valid(subset.len());
-                    log_if_enabled!(MIS_LOG_PATH, "M[{}, {subset:?}] = M[{}, S] + M[{}, S] - |S| = {left_size} + {right_size} - {len}", bag.id, left_child.id, right_child.id);
-                    table.put(bag.id, subset, left_size + right_size - len);
-                    constr_table[bag.id].push((Some(i), Some(j))); // Reconstruction.
-                }
-            }
+            _ => unreachable!("the kind of a bag fits its children"),
+        };
+        (dense, with_v)
+    }
 
-            _ => panic!("Unreachable"),
+    fn best_root_entry(&self, _bag: &Bag, table: &DenseBag) -> Option<(usize, Weight)> {
+        match table
+            .sizes
+            .iter()
+            .enumerate()
+            .max_by_key(|(_, &size)| size)?
+        {
+            (mask, &MisSize::Valid(weight)) => Some((mask, weight)),
+            (_, MisSize::Invalid) => None,
         }
     }
 
-    #[cfg(feature = "logging")]
-    {
-        log_if_enabled!(TABLE_LOG_PATH, "-------------\nFast Table\n-------------");
-        log_if_enabled!(TABLE_LOG_PATH, "{}", NtdAndFastTable { ntd, table: &table });
-        dump_construction_table("Fast Table", &constr_table, &table, &ntd.relations);
+    fn reconstruct(
+        &self,
+        bag: &Bag,
+        kind: BagKind,
+        with_v: &Option<BitSet>,
+        mask: usize,
+        mis: &mut HashSet<usize>,
+    ) -> (Option<usize>, Option<usize>) {
+        let dense = DenseBag::new(&bag.vertex_set);
+        mis.extend(dense.subset(mask as u64));
+
+        match kind {
+            BagKind::Leaf => (None, None),
+            BagKind::Introduce(v) => (
+                Some(remove_bit(mask as u64, dense.position(v)) as usize),
+                None,
+            ),
+            BagKind::Forget(v) => {
+                /* the position of `v` among the vertices of the child */
+                let p = dense.vertices.partition_point(|&u| u < v);
+                let without = insert_bit(mask as u64, p) as usize;
+                match with_v.as_ref().unwrap().contains(mask) {
+                    true => (Some(without | 1 << p), None),
+                    false => (Some(without), None),
+                }
+            }
+            BagKind::Copy => (Some(mask), None),
+            BagKind::Join => (Some(mask), Some(mask)),
+        }
     }
-
-    let result = reconstruct_mis(
-        &table,
-        ntd.td.root.unwrap(),
-        &constr_table,
-        &ntd.relations,
-        &adjaceny_matrix,
-    );
-
-    Ok((result.clone(), result.len()))
 }
 
 /// Finds the maximum independent set by checking all subsets of the graph for independence and
@@ -565,6 +501,15 @@ pub fn find_mis_fast(
 pub fn find_mis_exhaustive(
     adjaceny_matrix: &Vec<Vec<bool>>,
 ) -> Result<(HashSet<usize>, usize), FindMisError> {
+    find_weighted_mis_exhaustive(adjaceny_matrix, &vec![1; adjaceny_matrix.len()])
+}
+
+/// Same as [find_mis_exhaustive], but keeps the independent set with the largest total weight.
+/// Returns the set and its weight.
+pub fn find_weighted_mis_exhaustive(
+    adjaceny_matrix: &Vec<Vec<bool>>,
+    weights: &[Weight],
+) -> Result<(HashSet<usize>, Weight), FindMisError> {
     let is_independent = |subset: &HashSet<usize>| {
         subset
             .iter()
@@ -572,109 +517,26 @@ pub fn find_mis_exhaustive(
     };
 
     let mut max: HashSet<usize> = HashSet::new();
-    let combinations = usize::pow(2, adjaceny_matrix.len() as u32);
+    let mut max_weight = 0;
+    let combinations = 2f64.powi(adjaceny_matrix.len() as i32);
     for (i, subset) in SubsetIter::new(&FxHashSet::from_iter(0..adjaceny_matrix.len())).enumerate()
     {
         if i % 100000 == 0 {
             log_if_enabled!(
                 MIS_LOG_PATH,
                 "{i}/{combinations}, {}%",
-                f64::from(i as u32) / f64::from(combinations as u32) * 100.0
+                i as f64 / combinations * 100.0
             );
         }
         let subset2 = HashSet::from_iter(subset.into_iter());
-        if is_independent(&subset2) && subset2.len() > max.len() {
+        let weight = subset2.iter().map(|&v| weights[v]).sum();
+        if weight > max_weight && is_independent(&subset2) {
             max = subset2;
+            max_weight = weight;
         }
     }
 
-    let len = max.len();
-    Ok((max, len))
-}
-
-// TODO: If possible, merge the two `find_mis` algorithms.
-// TODO: Would it be a nice idea to log the steps of the algorithm (print to a string buffer)?
-
-/*
-pub struct AlgorithmData<Set>
-where
-    Set: Eq + std::fmt::Debug + Clone + Default,
-{
-    new_set: dyn Fn(dyn Iterator<Item = usize> + 'static) -> Set,
-}
-
-pub fn find_mis_merged<Set>(
-    adjaceny_matrix: Vec<Vec<bool>>,
-    ntd: &NiceTreeDecomposition,
-    table: &mut dyn DynTable<Set>,
-    algo_data: &AlgorithmData<Set>,
-) -> Result<(HashSet<usize>, usize), FindMisError>
-where
-    Set: Eq + std::fmt::Debug + Clone + Default,
-{
-    for bag in PostOrderIter::new(&ntd.td) {
-        let children = &ntd.relations.children[&bag.id];
-
-        match children.len() {
-            0 => {
-                table.put(bag.id, algo_data.new_set.call(vec![].iter()), MisSize::Valid(0));
-            }
-
-            1 => {}
-
-            2 => {}
-
-            _ => panic!("Unreachable"),
-        }
-    }
-    todo!()
-}
-*/
-
-/// Writes the construction table into a given file.
-fn dump_construction_table<Set>(
-    heading: &str,
-    constr_table: &ConstructionTable,
-    table: &dyn DynTable<Set>,
-    node_relations: &NodeRelations,
-) where
-    Set: Eq + std::fmt::Debug + Clone + Default,
-{
-    log_if_enabled!(CONSTR_TABLE_LOG_PATH, "---------------------------------------\n{heading}\n---------------------------------------");
-
-    for (bag_id, preds) in constr_table.iter().enumerate() {
-        for (set_id, preds) in preds.iter().enumerate() {
-            match preds {
-                (None, None) => {}
-
-                (Some(p), None) => {
-                    let child_id = node_relations.children[&bag_id][0];
-                    let (subset, size) = table.get_by_index(bag_id, set_id);
-                    let (child_subset, child_size) = table.get_by_index(child_id, *p);
-                    log_if_enabled!(
-                        CONSTR_TABLE_LOG_PATH,
-                        "{bag_id}'s set {set_id} ({:?}, {}) from child {child_id}'s set {p} ({:?}, {})",
-                        subset, size,
-                        child_subset, child_size,
-                    )
-                }
-
-                (Some(l), Some(r)) => {
-                    let left_id = node_relations.children[&bag_id][0];
-                    let right_id = node_relations.children[&bag_id][1];
-                    log_if_enabled!(
-                        CONSTR_TABLE_LOG_PATH,
-                        "{bag_id}'s set {set_id} {:?} from left child {left_id}'s set {l} {:?} and right child {right_id}'s set {r} {:?}",
-                        table.get_by_index(bag_id, set_id).0,
-                        table.get_by_index(left_id, *l).0,
-                        table.get_by_index(right_id, *r).0,
-                    )
-                }
-
-                _ => panic!("Unreachable"),
-            };
-        }
-    }
+    Ok((max, max_weight))
 }
 
 /// Finds the connected vertices of a set.
@@ -700,8 +562,12 @@ pub mod tests {
         graph::{
             approximated_td::{ApproximatedTD, TDBuilder},
             dcel::spanning_tree::SpanningTree,
+            dominating_set::OverlappingDonut,
             // mis_finder::{find_mis, find_mis_fast},
-            mis_finder::{find_connected_vertices, find_mis, find_mis_fast},
+            mis_finder::{
+                find_connected_vertices, find_mis, find_mis_fast, find_mis_weight_fast,
+                find_weighted_mis, find_weighted_mis_fast,
+            },
             nice_tree_decomp::NiceTreeDecomposition,
             node_relations::NodeRelations,
             tree_decomposition::td_write_to_dot,
@@ -712,7 +578,7 @@ pub mod tests {
     use fxhash::FxHashSet;
     use std::{fs::File, process::Command};
 
-    use super::{find_mis_exhaustive, ConstructionTable};
+    use super::find_mis_exhaustive;
 
     #[test]
     fn simple() {
@@ -730,11 +596,12 @@ pub mod tests {
 
         let td_rels = NodeRelations::new(&td);
 
-        let td_path = "td.dot";
-        let mut td_out = File::create(td_path).unwrap();
+        let td_path = std::env::temp_dir().join("td.dot").display().to_string();
+        let td_pdf = std::env::temp_dir().join("td.pdf").display().to_string();
+        let mut td_out = File::create(&td_path).unwrap();
         td_write_to_dot("td", &mut td_out, &td, &td_rels).unwrap();
         Command::new("dot")
-            .args(["-Tpdf", td_path, "-o", "td.pdf"])
+            .args(["-Tpdf", &td_path, "-o", &td_pdf])
             .spawn()
             .expect("dot command did not work.");
 
@@ -742,11 +609,12 @@ pub mod tests {
         let ntd_rels = NodeRelations::new(&ntd.td);
         assert!(ntd.validate(&td, &ntd_rels));
 
-        let ntd_path = "ntd.dot";
-        let mut ntd_out = File::create(ntd_path).unwrap();
+        let ntd_path = std::env::temp_dir().join("ntd.dot").display().to_string();
+        let ntd_pdf = std::env::temp_dir().join("ntd.pdf").display().to_string();
+        let mut ntd_out = File::create(&ntd_path).unwrap();
         td_write_to_dot("ntd", &mut ntd_out, &ntd.td, &ntd_rels).unwrap();
         Command::new("dot")
-            .args(["-Tpdf", ntd_path, "-o", "ntd.pdf"])
+            .args(["-Tpdf", &ntd_path, "-o", &ntd_pdf])
             .spawn()
             .expect("dot command did not work.");
 
@@ -769,19 +637,21 @@ pub mod tests {
         let ntd = NiceTreeDecomposition::from(&td);
         let ntd_rels = NodeRelations::new(&ntd.td);
 
-        let td_path = "td.dot";
-        let mut td_out = File::create(td_path).unwrap();
+        let td_path = std::env::temp_dir().join("td.dot").display().to_string();
+        let td_pdf = std::env::temp_dir().join("td.pdf").display().to_string();
+        let mut td_out = File::create(&td_path).unwrap();
         td_write_to_dot("td", &mut td_out, &td, &td_rels).unwrap();
         Command::new("dot")
-            .args(["-Tpdf", td_path, "-o", "td.pdf"])
+            .args(["-Tpdf", &td_path, "-o", &td_pdf])
             .spawn()
             .expect("dot command did not work.");
 
-        let ntd_path = "ntd.dot";
-        let mut ntd_out = File::create(ntd_path).unwrap();
+        let ntd_path = std::env::temp_dir().join("ntd.dot").display().to_string();
+        let ntd_pdf = std::env::temp_dir().join("ntd.pdf").display().to_string();
+        let mut ntd_out = File::create(&ntd_path).unwrap();
         td_write_to_dot("ntd", &mut ntd_out, &ntd.td, &ntd_rels).unwrap();
         Command::new("dot")
-            .args(["-Tpdf", ntd_path, "-o", "ntd.pdf"])
+            .args(["-Tpdf", &ntd_path, "-o", &ntd_pdf])
             .spawn()
             .expect("dot command did not work.");
 
@@ -812,19 +682,21 @@ pub mod tests {
         let ntd = NiceTreeDecomposition::from(&td);
         let ntd_rels = NodeRelations::new(&ntd.td);
 
-        let td_path = "td.dot";
-        let mut td_out = File::create(td_path).unwrap();
+        let td_path = std::env::temp_dir().join("td.dot").display().to_string();
+        let td_pdf = std::env::temp_dir().join("td.pdf").display().to_string();
+        let mut td_out = File::create(&td_path).unwrap();
         td_write_to_dot("td", &mut td_out, &td, &td_rels).unwrap();
         Command::new("dot")
-            .args(["-Tpdf", td_path, "-o", "td.pdf"])
+            .args(["-Tpdf", &td_path, "-o", &td_pdf])
             .spawn()
             .expect("dot command did not work.");
 
-        let ntd_path = "ntd.dot";
-        let mut ntd_out = File::create(ntd_path).unwrap();
+        let ntd_path = std::env::temp_dir().join("ntd.dot").display().to_string();
+        let ntd_pdf = std::env::temp_dir().join("ntd.pdf").display().to_string();
+        let mut ntd_out = File::create(&ntd_path).unwrap();
         td_write_to_dot("ntd", &mut ntd_out, &ntd.td, &ntd_rels).unwrap();
         Command::new("dot")
-            .args(["-Tpdf", ntd_path, "-o", "ntd.pdf"])
+            .args(["-Tpdf", &ntd_path, "-o", &ntd_pdf])
             .spawn()
             .expect("dot command did not work.");
 
@@ -840,6 +712,75 @@ pub mod tests {
         );
     }
 
+    #[test]
+    fn dense() {
+        for file in ["simple", "tri", "bsp3"] {
+            let dcel = read_graph_file_into_dcel_builder(&format!("data/{file}.graph"))
+                .unwrap()
+                .build();
+            let adjacency_matrix = dcel.adjacency_matrix();
+            let weights: Vec<usize> = (0..adjacency_matrix.len()).map(|v| v % 5 + 1).collect();
+            let donut = OverlappingDonut {
+                vertices: (0..adjacency_matrix.len()).collect(),
+                dominate: vec![true; adjacency_matrix.len()],
+            };
+            let ntd = NiceTreeDecomposition::from(&donut.tree_decomposition(&dcel).unwrap());
+
+            let (mis, weight) =
+                find_weighted_mis_fast(&adjacency_matrix, &weights, &ntd, false).unwrap();
+            assert!(find_connected_vertices(&mis, &adjacency_matrix).is_empty());
+            assert_eq!(weight, mis.iter().map(|&v| weights[v]).sum::<usize>());
+            let (_, expected) = find_weighted_mis(&adjacency_matrix, &weights, &ntd).unwrap();
+            assert_eq!(weight, expected, "{file}");
+            assert_eq!(
+                find_mis_weight_fast(&adjacency_matrix, &weights, &ntd, false).unwrap(),
+                weight
+            );
+        }
+    }
+
+    #[test]
+    fn dense_parallel() {
+        /* a path whose bags are large enough to be split between threads */
+        let n = 18;
+        let mut adjacency_matrix = vec![vec![false; n]; n];
+        for v in 1..n {
+            adjacency_matrix[v - 1][v] = true;
+            adjacency_matrix[v][v - 1] = true;
+        }
+        let weights: Vec<usize> = (0..n).map(|v| v * 7 % 4 + 1).collect();
+        let mut td = TreeDecomposition {
+            bags: Vec::new(),
+            root: None,
+            max_bag_size: 12,
+        };
+        let b0 = td.add_bag(FxHashSet::from_iter(0..12));
+        let b1 = td.add_bag(FxHashSet::from_iter(6..n));
+        td.add_edge(b0, b1);
+        let ntd = NiceTreeDecomposition::from(&td);
+
+        let sequential = find_weighted_mis_fast(&adjacency_matrix, &weights, &ntd, false).unwrap();
+        let pool = rayon::ThreadPoolBuilder::new()
+            .num_threads(4)
+            .build()
+            .unwrap();
+        let parallel = pool
+            .install(|| find_weighted_mis_fast(&adjacency_matrix, &weights, &ntd, true))
+            .unwrap();
+        assert_eq!(parallel, sequential);
+        assert_eq!(
+            find_weighted_mis(&adjacency_matrix, &weights, &ntd)
+                .unwrap()
+                .1,
+            sequential.1
+        );
+        assert_eq!(
+            pool.install(|| find_mis_weight_fast(&adjacency_matrix, &weights, &ntd, true))
+                .unwrap(),
+            sequential.1
+        );
+    }
+
     #[test]
     fn exhaustive() {
         let mut dcel_b = read_graph_file_into_dcel_builder("data/problem.graph").unwrap();
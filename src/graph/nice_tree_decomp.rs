@@ -287,11 +287,12 @@ pub mod tests {
 
         let td_rels = NodeRelations::new(&td);
 
-        let td_path = "td.dot";
-        let mut td_out = File::create(td_path).unwrap();
+        let td_path = std::env::temp_dir().join("td.dot").display().to_string();
+        let td_pdf = std::env::temp_dir().join("td.pdf").display().to_string();
+        let mut td_out = File::create(&td_path).unwrap();
         td_write_to_dot("td", &mut td_out, &td, &td_rels).unwrap();
         Command::new("dot")
-            .args(["-Tpdf", td_path, "-o", "td.pdf"])
+            .args(["-Tpdf", &td_path, "-o", &td_pdf])
             .spawn()
             .expect("dot command did not work.");
 
@@ -299,11 +300,12 @@ pub mod tests {
         let ntd_rels = NodeRelations::new(&ntd.td);
         assert!(ntd.validate(&td, &ntd_rels));
 
-        let ntd_path = "ntd.dot";
-        let mut ntd_out = File::create(ntd_path).unwrap();
+        let ntd_path = std::env::temp_dir().join("ntd.dot").display().to_string();
+        let ntd_pdf = std::env::temp_dir().join("ntd.pdf").display().to_string();
+        let mut ntd_out = File::create(&ntd_path).unwrap();
         td_write_to_dot("ntd", &mut ntd_out, &ntd.td, &ntd_rels).unwrap();
         Command::new("dot")
-            .args(["-Tpdf", ntd_path, "-o", "ntd.pdf"])
+            .args(["-Tpdf", &ntd_path, "-o", &ntd_pdf])
             .spawn()
             .expect("dot command did not work.");
     }
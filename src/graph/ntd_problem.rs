@@ -0,0 +1,437 @@
+use std::collections::{HashMap, HashSet};
+use std::hash::Hash;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Mutex;
+
+use arboretum_td::tree_decomposition::Bag;
+
+use super::iterators::post_order::PostOrderIter;
+use super::nice_tree_decomp::NiceTreeDecomposition;
+
+/// A single entry of the dynamic table of a bag.
+#[derive(Debug, Clone)]
+pub struct TableEntry<State, Value> {
+    /// The state of the bag vertices, e.g. the subset of the bag that is in the solution.
+    pub state: State,
+    pub value: Value,
+    /// The indices of the entries of the (left and right) child this entry was constructed from.
+    pub constructed_from: (Option<usize>, Option<usize>),
+}
+
+impl<State, Value> TableEntry<State, Value> {
+    pub fn new(
+        state: State,
+        value: Value,
+        constructed_from: (Option<usize>, Option<usize>),
+    ) -> Self {
+        TableEntry {
+            state,
+            value,
+            constructed_from,
+        }
+    }
+}
+
+/// The dynamic table of a single bag. The entries can be found by their state.
+pub struct BagTable<State, Value> {
+    entries: Vec<TableEntry<State, Value>>,
+    index: HashMap<State, usize>,
+}
+
+impl<State: Clone + Eq + Hash, Value: Copy> BagTable<State, Value> {
+    fn new(entries: Vec<TableEntry<State, Value>>) -> Self {
+        let index = entries
+            .iter()
+            .enumerate()
+            .map(|(i, entry)| (entry.state.clone(), i))
+            .collect();
+        BagTable { entries, index }
+    }
+
+    /// Returns the index and the value of the entry with the given state.
+    /// Panics if the table has no such entry.
+    pub fn get(&self, state: &State) -> (usize, Value) {
+        let i = self.index[state];
+        (i, self.entries[i].value)
+    }
+
+    pub fn entries(&self) -> &[TableEntry<State, Value>] {
+        &self.entries
+    }
+}
+
+/// A problem that is solved by dynamic programming over a [NiceTreeDecomposition].
+///
+/// [solve_ntd_problem] calls the function for the kind of each bag, which computes all entries of
+/// the bag from the tables of its children. Bags with the same vertices as their only child get a
+/// copy of its table. Afterwards, the solution is reconstructed from the best entry of the root by
+/// following [TableEntry::constructed_from].
+pub trait NtdProblem: Sync {
+    /// The state of the bag vertices, e.g. the subset of the bag that is in the solution.
+    type State: Clone + Eq + Hash + Send + std::fmt::Debug;
+    /// The value of a partial solution, e.g. the weight of the set.
+    type Value: Copy + Send + std::fmt::Debug;
+
+    /// Computes the table of a leaf.
+    fn leaf(&self, bag: &Bag) -> Vec<TableEntry<Self::State, Self::Value>>;
+
+    /// Computes the table of a bag that introduces `v` to the vertices of its child.
+    fn introduce(
+        &self,
+        bag: &Bag,
+        v: usize,
+        child: &BagTable<Self::State, Self::Value>,
+    ) -> Vec<TableEntry<Self::State, Self::Value>>;
+
+    /// Computes the table of a bag that forgets the vertex `v` of its child.
+    fn forget(
+        &self,
+        bag: &Bag,
+        v: usize,
+        child: &BagTable<Self::State, Self::Value>,
+    ) -> Vec<TableEntry<Self::State, Self::Value>>;
+
+    /// Computes the table of a bag with two children that have the same vertices as the bag.
+    fn join(
+        &self,
+        bag: &Bag,
+        left: &BagTable<Self::State, Self::Value>,
+        right: &BagTable<Self::State, Self::Value>,
+    ) -> Vec<TableEntry<Self::State, Self::Value>>;
+
+    /// Returns the index of the best entry of the root table, or `None` if there is no solution.
+    fn best_root_entry(
+        &self,
+        bag: &Bag,
+        table: &BagTable<Self::State, Self::Value>,
+    ) -> Option<usize>;
+
+    /// Adds the vertices of the solution that are part of `state` to `solution`.
+    /// This is called for one entry of every bag during the reconstruction.
+    fn reconstruct(&self, bag: &Bag, state: &Self::State, solution: &mut HashSet<usize>);
+}
+
+/// The kind of a bag of a [NiceTreeDecomposition], which decides how its table is computed from
+/// the tables of its children.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BagKind {
+    Leaf,
+    /// The bag has the vertices of its child and `v`.
+    Introduce(usize),
+    /// The bag has the vertices of its child except `v`.
+    Forget(usize),
+    /// The bag has the same vertices as its only child. [NiceTreeDecomposition::from] creates
+    /// such a bag if a bag of the tree decomposition has a child with the same vertices, so no
+    /// vertex is introduced or forgotten in between. The table of the child is copied.
+    Copy,
+    /// The bag has two children with the same vertices as the bag.
+    Join,
+}
+
+impl BagKind {
+    /// Returns the kind of the bag, or `None` if it has more than two children.
+    pub fn of(ntd: &NiceTreeDecomposition, bag: &Bag) -> Option<BagKind> {
+        match ntd.relations.children[&bag.id][..] {
+            [] => Some(BagKind::Leaf),
+            [child] => {
+                let child = &ntd.td.bags[child].vertex_set;
+                if let Some(&v) = bag.vertex_set.difference(child).next() {
+                    Some(BagKind::Introduce(v))
+                } else if let Some(&v) = child.difference(&bag.vertex_set).next() {
+                    Some(BagKind::Forget(v))
+                } else {
+                    Some(BagKind::Copy)
+                }
+            }
+            [_, _] => Some(BagKind::Join),
+            _ => None,
+        }
+    }
+}
+
+/// How the tables of a dynamic program over a [NiceTreeDecomposition] are stored and computed,
+/// the table parameter of [solve_ntd_tables]. The tables of a [NtdProblem] are [BagTable]s, other
+/// implementations can store the tables in a way that fits the problem, e.g. indexed by masks.
+///
+/// The table of a bag is computed from the tables of its children, which are consumed, so only
+/// the tables of bags whose parent was not computed yet are in memory. The reconstruction only
+/// needs the trace of every bag, which tells from which entries of the children an entry was
+/// computed.
+pub trait NtdTables: Sync {
+    /// The table of a single bag.
+    type Table: Send;
+    /// What the reconstruction needs to know about a bag once its table is freed.
+    type Trace: Send;
+    /// The value of a partial solution, e.g. the weight of the set.
+    type Value: Copy;
+
+    /// Computes the table of a bag and its trace from the tables of its children.
+    fn compute(
+        &self,
+        bag: &Bag,
+        kind: BagKind,
+        children: Vec<Self::Table>,
+    ) -> (Self::Table, Self::Trace);
+
+    /// Returns the index and the value of the best entry of the root table, or `None` if there is
+    /// no solution.
+    fn best_root_entry(&self, bag: &Bag, table: &Self::Table) -> Option<(usize, Self::Value)>;
+
+    /// Adds the vertices of the solution that are part of entry `index` of the bag to `solution`
+    /// and returns the indices of the entries of the (left and right) child it was computed from.
+    fn reconstruct(
+        &self,
+        bag: &Bag,
+        kind: BagKind,
+        trace: &Self::Trace,
+        index: usize,
+        solution: &mut HashSet<usize>,
+    ) -> (Option<usize>, Option<usize>);
+}
+
+/* the tables of a [NtdProblem], the trace of a bag is the state and the origin of every entry */
+struct ProblemTables<'a, P>(&'a P);
+
+impl<P: NtdProblem> NtdTables for ProblemTables<'_, P> {
+    type Table = BagTable<P::State, P::Value>;
+    type Trace = Vec<(P::State, (Option<usize>, Option<usize>))>;
+    type Value = P::Value;
+
+    fn compute(
+        &self,
+        bag: &Bag,
+        kind: BagKind,
+        children: Vec<Self::Table>,
+    ) -> (Self::Table, Self::Trace) {
+        let problem = self.0;
+        let entries = match (kind, &children[..]) {
+            (BagKind::Introduce(v), [child]) => problem.introduce(bag, v, child),
+            (BagKind::Forget(v), [child]) => problem.forget(bag, v, child),
+            (BagKind::Copy, [child]) => child
+                .entries()
+                .iter()
+                .enumerate()
+                .map(|(i, entry)| {
+                    TableEntry::new(entry.state.clone(), entry.value, (Some(i), None))
+                })
+                .collect(),
+            (BagKind::Join, [left, right]) => problem.join(bag, left, right),
+            (BagKind::Leaf, []) => problem.leaf(bag),
+            _ => unreachable!("the kind of a bag fits its children"),
+        };
+        let trace = entries
+            .iter()
+            .map(|entry| (entry.state.clone(), entry.constructed_from))
+            .collect();
+        (BagTable::new(entries), trace)
+    }
+
+    fn best_root_entry(&self, bag: &Bag, table: &Self::Table) -> Option<(usize, P::Value)> {
+        let i = self.0.best_root_entry(bag, table)?;
+        Some((i, table.entries[i].value))
+    }
+
+    fn reconstruct(
+        &self,
+        bag: &Bag,
+        _kind: BagKind,
+        trace: &Self::Trace,
+        index: usize,
+        solution: &mut HashSet<usize>,
+    ) -> (Option<usize>, Option<usize>) {
+        let (state, constructed_from) = &trace[index];
+        self.0.reconstruct(bag, state, solution);
+        *constructed_from
+    }
+}
+
+/// Possible errors of [solve_ntd_tables].
+#[derive(Debug, PartialEq, Eq)]
+pub enum NtdError {
+    /// The decomposition has no root or a bag with more than two children.
+    InvalidNiceTD,
+    /// No entry of the root table is a solution.
+    NoSolution,
+}
+
+/// Solves the problem on the nice tree decomposition, see [NtdProblem].
+/// Returns the reconstructed solution and the value of the best root entry.
+pub fn solve_ntd_problem<P: NtdProblem>(
+    problem: &P,
+    ntd: &NiceTreeDecomposition,
+) -> Result<(HashSet<usize>, P::Value), NtdError> {
+    solve_ntd_tables(&ProblemTables(problem), ntd, true, false)
+}
+
+/// Runs the dynamic program of `tables` on the nice tree decomposition, see [NtdTables].
+/// The decomposition is traversed in post order (left child, right child, parent), afterwards the
+/// solution is reconstructed from the best entry of the root by following the traces.
+/// Returns the solution, which is empty without `reconstruct`, and the value of the best root
+/// entry. Without `reconstruct`, no traces are kept.
+///
+/// With `parallel`, every bag is computed as soon as the tables of all its children are, so
+/// independent subtrees are computed at the same time on the current rayon thread pool. The
+/// result is the same as without it.
+pub fn solve_ntd_tables<T: NtdTables>(
+    tables: &T,
+    ntd: &NiceTreeDecomposition,
+    reconstruct: bool,
+    parallel: bool,
+) -> Result<(HashSet<usize>, T::Value), NtdError> {
+    let root_id = ntd.td.root.ok_or(NtdError::InvalidNiceTD)?;
+    let kinds: Vec<BagKind> = ntd
+        .td
+        .bags
+        .iter()
+        .map(|bag| BagKind::of(ntd, bag))
+        .collect::<Option<_>>()
+        .ok_or(NtdError::InvalidNiceTD)?;
+
+    let traversal = Traversal {
+        tables,
+        ntd,
+        kinds: &kinds,
+        reconstruct,
+        bag_tables: Mutex::new((0..kinds.len()).map(|_| None).collect()),
+        traces: Mutex::new((0..kinds.len()).map(|_| None).collect()),
+    };
+    match parallel {
+        true => traversal.compute_parallel(),
+        false => PostOrderIter::new(&ntd.td).for_each(|bag| traversal.compute(bag.id)),
+    }
+    let bag_tables = traversal.bag_tables.into_inner().unwrap();
+    let traces = traversal.traces.into_inner().unwrap();
+
+    let root = bag_tables[root_id].as_ref().unwrap();
+    let (root_index, value) = tables
+        .best_root_entry(&ntd.td.bags[root_id], root)
+        .ok_or(NtdError::NoSolution)?;
+
+    let mut solution = HashSet::new();
+    let mut stack = match reconstruct {
+        true => vec![(root_id, root_index)],
+        false => vec![],
+    };
+    while let Some((bag_id, index)) = stack.pop() {
+        let trace = traces[bag_id].as_ref().unwrap();
+        let bag = &ntd.td.bags[bag_id];
+        let (left, right) = tables.reconstruct(bag, kinds[bag_id], trace, index, &mut solution);
+
+        let children = &ntd.relations.children[&bag_id];
+        if let Some(i) = left {
+            stack.push((children[0], i));
+        }
+        if let Some(j) = right {
+            stack.push((children[1], j));
+        }
+    }
+
+    Ok((solution, value))
+}
+
+/* everything the traversal of [solve_ntd_tables] shares between its tasks */
+struct Traversal<'a, T: NtdTables> {
+    tables: &'a T,
+    ntd: &'a NiceTreeDecomposition,
+    kinds: &'a [BagKind],
+    reconstruct: bool,
+    /// The tables of the bags whose parent was not computed yet.
+    bag_tables: Mutex<Vec<Option<T::Table>>>,
+    traces: Mutex<Vec<Option<T::Trace>>>,
+}
+
+impl<T: NtdTables> Traversal<'_, T> {
+    /* computes the table of a bag, whose children have to be computed already */
+    fn compute(&self, bag_id: usize) {
+        let children = {
+            let mut bag_tables = self.bag_tables.lock().unwrap();
+            self.ntd.relations.children[&bag_id]
+                .iter()
+                .map(|&child| bag_tables[child].take().unwrap())
+                .collect()
+        };
+        let bag = &self.ntd.td.bags[bag_id];
+        let (table, trace) = self.tables.compute(bag, self.kinds[bag_id], children);
+        self.bag_tables.lock().unwrap()[bag_id] = Some(table);
+        if self.reconstruct {
+            self.traces.lock().unwrap()[bag_id] = Some(trace);
+        }
+    }
+
+    /* computes every bag as soon as the tables of all its children are */
+    fn compute_parallel(&self) {
+        /* the number of children of each bag that are not computed yet */
+        let bag_count = self.kinds.len();
+        let mut parent = vec![None; bag_count];
+        let pending: Vec<AtomicUsize> = (0..bag_count)
+            .map(|bag| {
+                let children = &self.ntd.relations.children[&bag];
+                children.iter().for_each(|&child| parent[child] = Some(bag));
+                AtomicUsize::new(children.len())
+            })
+            .collect();
+
+        fn compute<'s, T: NtdTables>(
+            scope: &rayon::Scope<'s>,
+            bag_id: usize,
+            traversal: &'s Traversal<'s, T>,
+            parent: &'s [Option<usize>],
+            pending: &'s [AtomicUsize],
+        ) {
+            traversal.compute(bag_id);
+            /* the last child to finish computes the parent */
+            if let Some(parent_id) = parent[bag_id] {
+                if pending[parent_id].fetch_sub(1, Ordering::AcqRel) == 1 {
+                    scope.spawn(move |scope| compute(scope, parent_id, traversal, parent, pending));
+                }
+            }
+        }
+
+        let (parent, pending) = (&parent, &pending);
+        rayon::scope(|scope| {
+            for leaf in (0..bag_count).filter(|&bag| self.kinds[bag] == BagKind::Leaf) {
+                scope.spawn(move |scope| compute(scope, leaf, self, parent, pending));
+            }
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{solve_ntd_problem, solve_ntd_tables, ProblemTables};
+    use crate::graph::dominating_set::OverlappingDonut;
+    use crate::graph::mis_finder::{find_weighted_mis_exhaustive, IndependentSet, MisSize};
+    use crate::graph::nice_tree_decomp::NiceTreeDecomposition;
+    use crate::read_graph_file_into_dcel_builder;
+
+    #[test]
+    fn weighted_independent_set() {
+        for file in ["tree", "tri", "simple"] {
+            let graph = read_graph_file_into_dcel_builder(&format!("data/{file}.graph"))
+                .unwrap()
+                .build();
+            let adjacency = graph.adjacency_matrix();
+            let weights: Vec<usize> = (0..graph.num_vertices()).map(|v| v % 4 + 1).collect();
+
+            /* the whole graph as a single donut */
+            let donut = OverlappingDonut {
+                vertices: (0..graph.num_vertices()).collect(),
+                dominate: vec![true; graph.num_vertices()],
+            };
+            let td = donut.tree_decomposition(&graph).unwrap();
+            let ntd = NiceTreeDecomposition::from(&td);
+
+            let problem = IndependentSet::new(&adjacency, &weights);
+            let (set, value) = solve_ntd_problem(&problem, &ntd).unwrap();
+            let (_, parallel) =
+                solve_ntd_tables(&ProblemTables(&problem), &ntd, false, true).unwrap();
+            assert_eq!(parallel, value);
+            let weight = set.iter().map(|&v| weights[v]).sum();
+            assert_eq!(value, MisSize::Valid(weight));
+            assert!(set.iter().all(|&u| set.iter().all(|&v| !adjacency[u][v])));
+
+            let (_, optimum) = find_weighted_mis_exhaustive(&adjacency, &weights).unwrap();
+            assert_eq!(weight, optimum, "{file}");
+        }
+    }
+}
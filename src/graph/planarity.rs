@@ -0,0 +1,825 @@
+use std::collections::{HashMap, HashSet};
+
+use crate::graph::dcel::arc::ArcId;
+use crate::graph::dcel::vertex::VertexId;
+use crate::graph::graph_file_reader::ArcList;
+
+type EdgeId = usize;
+
+/// The planarity tests that search the witness of a non-planar graph stop after this many edges
+/// in total, see [NotPlanar::witness].
+pub const WITNESS_BUDGET: usize = 1 << 26;
+
+/// The two graphs one of whose subdivisions is contained in every non-planar graph.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum KuratowskiSubgraph {
+    K5,
+    K33,
+}
+
+/// Represents a graph that is not planar.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NotPlanar {
+    /// A subdivision of K5 or K3,3 that is contained in the graph, or `None` if the search for
+    /// it exceeded [WITNESS_BUDGET].
+    pub witness: Option<Vec<(VertexId, VertexId)>>,
+}
+
+impl NotPlanar {
+    pub fn kind(&self) -> Option<KuratowskiSubgraph> {
+        let mut degree: HashMap<VertexId, usize> = HashMap::new();
+        for &(u, v) in self.witness.as_ref()? {
+            *degree.entry(u).or_default() += 1;
+            *degree.entry(v).or_default() += 1;
+        }
+        match degree.values().filter(|&&d| d > 3).count() {
+            0 => Some(KuratowskiSubgraph::K33),
+            _ => Some(KuratowskiSubgraph::K5),
+        }
+    }
+}
+
+impl std::error::Error for NotPlanar {}
+
+impl std::fmt::Display for NotPlanar {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let (Some(kind), Some(witness)) = (self.kind(), &self.witness) else {
+            return write!(
+                f,
+                "the graph is not planar, it is too big to search a witness"
+            );
+        };
+        let kind = match kind {
+            KuratowskiSubgraph::K5 => "K5",
+            KuratowskiSubgraph::K33 => "K3,3",
+        };
+        write!(
+            f,
+            "the graph is not planar, it contains a subdivision of {kind} with the edges"
+        )?;
+        for (u, v) in witness {
+            write!(f, " {u}-{v}")?;
+        }
+        Ok(())
+    }
+}
+
+/// Computes a planar embedding of the graph with the given undirected edges with the
+/// left-right planarity test. Self-loops and parallel edges are ignored.
+///
+/// The returned arcs contain both directions of every edge and are grouped by their source
+/// vertex in counterclockwise order, so `DcelBuilder::from(&arc_list)` builds the embedding.
+/// If the graph is not planar, a Kuratowski subgraph is returned instead, see
+/// [NotPlanar::witness].
+pub fn planar_embedding(
+    vertex_count: usize,
+    edges: &[(VertexId, VertexId)],
+) -> Result<ArcList, NotPlanar> {
+    let edges = simple_edges(edges);
+    match LrPlanarity::new(vertex_count, &edges).embed() {
+        Some(rotations) => {
+            let arcs = rotations
+                .iter()
+                .enumerate()
+                .flat_map(|(v, rotation)| rotation.iter().map(move |&w| (v, w)))
+                .collect();
+            Ok(ArcList {
+                vertex_count,
+                arcs,
+                weights: None,
+            })
+        }
+        None => Err(NotPlanar {
+            witness: kuratowski_witness(edges),
+        }),
+    }
+}
+
+/// Returns true if the graph with the given undirected edges is planar.
+pub fn is_planar(vertex_count: usize, edges: &[(VertexId, VertexId)]) -> bool {
+    LrPlanarity::new(vertex_count, &simple_edges(edges)).test()
+}
+
+/* removes self-loops and parallel edges */
+fn simple_edges(edges: &[(VertexId, VertexId)]) -> Vec<(VertexId, VertexId)> {
+    let mut seen = HashSet::new();
+    edges
+        .iter()
+        .filter(|(u, v)| u != v && seen.insert((*u.min(v), *u.max(v))))
+        .copied()
+        .collect()
+}
+
+/* Finds a Kuratowski subgraph of a non-planar graph. The witness and the remaining edges are
+ * not planar together, so a binary search finds the shortest prefix of the remaining edges that
+ * makes the witness non-planar. Its last edge is needed and added to the witness, the edges after
+ * it are not needed. This takes O(w log m) planarity tests of at most m edges each, where w is
+ * the size of the witness, so the search gives up after the tests saw WITNESS_BUDGET edges. */
+fn kuratowski_witness(mut rest: Vec<(VertexId, VertexId)>) -> Option<Vec<(VertexId, VertexId)>> {
+    let mut budget = WITNESS_BUDGET;
+    let mut is_planar = |edges: &[(VertexId, VertexId)]| {
+        budget = budget.checked_sub(edges.len())?;
+        Some(is_planar_compact(edges))
+    };
+    let mut witness = vec![];
+    while is_planar(&witness)? {
+        /* the witness is planar with the first `low` remaining edges, but not with `high` */
+        let (mut low, mut high) = (0, rest.len());
+        while high - low > 1 {
+            let mid = (low + high) / 2;
+            let candidate: Vec<(VertexId, VertexId)> =
+                witness.iter().chain(&rest[..mid]).copied().collect();
+            match is_planar(&candidate)? {
+                true => low = mid,
+                false => high = mid,
+            }
+        }
+        witness.push(rest[high - 1]);
+        rest.truncate(high - 1);
+    }
+    Some(witness)
+}
+
+/* tests planarity after relabeling the vertices of the edges to 0..n */
+fn is_planar_compact(edges: &[(VertexId, VertexId)]) -> bool {
+    let mut ids: HashMap<VertexId, VertexId> = HashMap::new();
+    let edges: Vec<(VertexId, VertexId)> = edges
+        .iter()
+        .map(|&(u, v)| {
+            let next = ids.len();
+            let u = *ids.entry(u).or_insert(next);
+            let next = ids.len();
+            let v = *ids.entry(v).or_insert(next);
+            (u, v)
+        })
+        .collect();
+    LrPlanarity::new(ids.len(), &edges).test()
+}
+
+#[derive(Debug, Clone, Copy, Default)]
+struct Interval {
+    low: Option<EdgeId>,
+    high: Option<EdgeId>,
+}
+
+impl Interval {
+    fn new(low: EdgeId, high: EdgeId) -> Self {
+        Interval {
+            low: Some(low),
+            high: Some(high),
+        }
+    }
+
+    fn is_empty(&self) -> bool {
+        self.low.is_none() && self.high.is_none()
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default)]
+struct ConflictPair {
+    left: Interval,
+    right: Interval,
+}
+
+impl ConflictPair {
+    fn swap(&mut self) {
+        std::mem::swap(&mut self.left, &mut self.right);
+    }
+}
+
+/// State of the left-right planarity test by Ulrik Brandes ("The Left-Right Planarity Test").
+/// All three depth first searches are iterative, so deep DFS trees do not overflow the stack.
+///
+/// Every undirected edge gets oriented by the first DFS. The arcs of an oriented edge `e` are
+/// `2 * e` (in direction of the edge) and `2 * e + 1` (against it).
+struct LrPlanarity {
+    vertex_count: usize,
+    adjacency: Vec<Vec<(VertexId, EdgeId)>>,
+    src: Vec<VertexId>,
+    dst: Vec<VertexId>,
+    oriented: Vec<bool>,
+    roots: Vec<VertexId>,
+
+    height: Vec<Option<usize>>,
+    parent_edge: Vec<Option<EdgeId>>,
+    lowpt: Vec<usize>,
+    lowpt2: Vec<usize>,
+    nesting_depth: Vec<isize>,
+    /* outgoing edges of each vertex in the order they were oriented */
+    out_edges: Vec<Vec<EdgeId>>,
+    ordered_adjs: Vec<Vec<EdgeId>>,
+
+    reference: Vec<Option<EdgeId>>,
+    side: Vec<isize>,
+    stack: Vec<ConflictPair>,
+    stack_bottom: Vec<usize>,
+    lowpt_edge: Vec<Option<EdgeId>>,
+
+    /* state of the iterative DFS */
+    ind: Vec<usize>,
+    skip_init: Vec<bool>,
+}
+
+impl LrPlanarity {
+    fn new(vertex_count: usize, edges: &[(VertexId, VertexId)]) -> Self {
+        let mut adjacency = vec![vec![]; vertex_count];
+        for (e, &(u, v)) in edges.iter().enumerate() {
+            adjacency[u].push((v, e));
+            adjacency[v].push((u, e));
+        }
+        let edge_count = edges.len();
+        LrPlanarity {
+            vertex_count,
+            adjacency,
+            src: vec![0; edge_count],
+            dst: vec![0; edge_count],
+            oriented: vec![false; edge_count],
+            roots: vec![],
+            height: vec![None; vertex_count],
+            parent_edge: vec![None; vertex_count],
+            lowpt: vec![0; edge_count],
+            lowpt2: vec![0; edge_count],
+            nesting_depth: vec![0; edge_count],
+            out_edges: vec![vec![]; vertex_count],
+            ordered_adjs: vec![],
+            reference: vec![None; edge_count],
+            side: vec![1; edge_count],
+            stack: vec![],
+            stack_bottom: vec![0; edge_count],
+            lowpt_edge: vec![None; edge_count],
+            ind: vec![],
+            skip_init: vec![],
+        }
+    }
+
+    fn edge_count(&self) -> usize {
+        self.src.len()
+    }
+
+    fn reset_dfs(&mut self) {
+        self.ind = vec![0; self.vertex_count];
+        self.skip_init = vec![false; self.edge_count()];
+    }
+
+    /* orients the edges and runs the left-right test */
+    fn test(&mut self) -> bool {
+        if self.vertex_count > 2 && self.edge_count() > 3 * self.vertex_count - 6 {
+            return false;
+        }
+
+        self.reset_dfs();
+        for v in 0..self.vertex_count {
+            if self.height[v].is_none() {
+                self.height[v] = Some(0);
+                self.roots.push(v);
+                self.dfs_orientation(v);
+            }
+        }
+
+        self.ordered_adjs = self.sorted_out_edges();
+        self.reset_dfs();
+        for i in 0..self.roots.len() {
+            if !self.dfs_testing(self.roots[i]) {
+                return false;
+            }
+        }
+        true
+    }
+
+    /* returns the neighbors of every vertex in counterclockwise order, if the graph is planar */
+    fn embed(mut self) -> Option<Vec<Vec<VertexId>>> {
+        if !self.test() {
+            return None;
+        }
+
+        for e in 0..self.edge_count() {
+            self.nesting_depth[e] *= self.sign(e);
+        }
+        self.ordered_adjs = self.sorted_out_edges();
+
+        let mut embedding = Embedding::new(self.vertex_count, self.edge_count());
+        for v in 0..self.vertex_count {
+            let mut previous = None;
+            for &e in &self.ordered_adjs[v] {
+                embedding.add_cw(v, 2 * e, previous);
+                previous = Some(2 * e);
+            }
+        }
+
+        self.reset_dfs();
+        let mut left_ref = vec![None; self.vertex_count];
+        let mut right_ref = vec![None; self.vertex_count];
+        for i in 0..self.roots.len() {
+            self.dfs_embedding(self.roots[i], &mut embedding, &mut left_ref, &mut right_ref);
+        }
+
+        Some(
+            (0..self.vertex_count)
+                .map(|v| {
+                    embedding
+                        .ccw_order(v)
+                        .into_iter()
+                        .map(|arc| self.arc_target(arc))
+                        .collect()
+                })
+                .collect(),
+        )
+    }
+
+    fn arc_target(&self, arc: ArcId) -> VertexId {
+        match arc % 2 {
+            0 => self.dst[arc / 2],
+            _ => self.src[arc / 2],
+        }
+    }
+
+    /* sorts the outgoing edges of each vertex by their nesting depth */
+    fn sorted_out_edges(&self) -> Vec<Vec<EdgeId>> {
+        self.out_edges
+            .iter()
+            .map(|edges| {
+                let mut edges = edges.clone();
+                edges.sort_by_key(|&e| self.nesting_depth[e]);
+                edges
+            })
+            .collect()
+    }
+
+    fn dfs_orientation(&mut self, root: VertexId) {
+        let mut dfs_stack = vec![root];
+        'dfs: while let Some(v) = dfs_stack.pop() {
+            let parent = self.parent_edge[v];
+            let height_v = self.height[v].unwrap();
+            while self.ind[v] < self.adjacency[v].len() {
+                let (w, vw) = self.adjacency[v][self.ind[v]];
+                if !self.skip_init[vw] {
+                    if self.oriented[vw] {
+                        self.ind[v] += 1;
+                        continue;
+                    }
+                    self.oriented[vw] = true;
+                    self.src[vw] = v;
+                    self.dst[vw] = w;
+                    self.out_edges[v].push(vw);
+                    self.lowpt[vw] = height_v;
+                    self.lowpt2[vw] = height_v;
+                    match self.height[w] {
+                        None => {
+                            /* tree edge */
+                            self.parent_edge[w] = Some(vw);
+                            self.height[w] = Some(height_v + 1);
+                            self.skip_init[vw] = true;
+                            dfs_stack.push(v);
+                            dfs_stack.push(w);
+                            continue 'dfs;
+                        }
+                        /* back edge */
+                        Some(height_w) => self.lowpt[vw] = height_w,
+                    }
+                }
+
+                /* determine nesting graph */
+                self.nesting_depth[vw] = 2 * self.lowpt[vw] as isize;
+                if self.lowpt2[vw] < height_v {
+                    /* chordal */
+                    self.nesting_depth[vw] += 1;
+                }
+
+                /* update lowpoints of the parent edge */
+                if let Some(e) = parent {
+                    if self.lowpt[vw] < self.lowpt[e] {
+                        self.lowpt2[e] = self.lowpt[e].min(self.lowpt2[vw]);
+                        self.lowpt[e] = self.lowpt[vw];
+                    } else if self.lowpt[vw] > self.lowpt[e] {
+                        self.lowpt2[e] = self.lowpt2[e].min(self.lowpt[vw]);
+                    } else {
+                        self.lowpt2[e] = self.lowpt2[e].min(self.lowpt2[vw]);
+                    }
+                }
+                self.ind[v] += 1;
+            }
+        }
+    }
+
+    fn dfs_testing(&mut self, root: VertexId) -> bool {
+        let mut dfs_stack = vec![root];
+        'dfs: while let Some(v) = dfs_stack.pop() {
+            let parent = self.parent_edge[v];
+            let height_v = self.height[v].unwrap();
+            while self.ind[v] < self.ordered_adjs[v].len() {
+                let ei = self.ordered_adjs[v][self.ind[v]];
+                let w = self.dst[ei];
+                if !self.skip_init[ei] {
+                    self.stack_bottom[ei] = self.stack.len();
+                    if self.parent_edge[w] == Some(ei) {
+                        /* tree edge */
+                        self.skip_init[ei] = true;
+                        dfs_stack.push(v);
+                        dfs_stack.push(w);
+                        continue 'dfs;
+                    }
+                    /* back edge */
+                    self.lowpt_edge[ei] = Some(ei);
+                    self.stack.push(ConflictPair {
+                        left: Interval::default(),
+                        right: Interval::new(ei, ei),
+                    });
+                }
+
+                /* integrate new return edges */
+                if self.lowpt[ei] < height_v {
+                    /* only the root has no parent and no edge returns below the root */
+                    let e = parent.unwrap();
+                    if self.ind[v] == 0 {
+                        self.lowpt_edge[e] = self.lowpt_edge[ei];
+                    } else if !self.add_constraints(ei, e) {
+                        return false;
+                    }
+                }
+                self.ind[v] += 1;
+            }
+
+            /* remove back edges returning to the parent */
+            if let Some(e) = parent {
+                self.remove_back_edges(e);
+            }
+        }
+        true
+    }
+
+    fn conflicting(&self, interval: &Interval, edge: EdgeId) -> bool {
+        match interval.high {
+            Some(high) => self.lowpt[high] > self.lowpt[edge],
+            None => false,
+        }
+    }
+
+    fn lowest(&self, pair: &ConflictPair) -> usize {
+        match (pair.left.low, pair.right.low) {
+            (None, Some(right)) => self.lowpt[right],
+            (Some(left), None) => self.lowpt[left],
+            (Some(left), Some(right)) => self.lowpt[left].min(self.lowpt[right]),
+            (None, None) => panic!("Empty conflict pair on the stack"),
+        }
+    }
+
+    fn add_constraints(&mut self, ei: EdgeId, e: EdgeId) -> bool {
+        let mut pair = ConflictPair::default();
+
+        /* merge return edges of ei into pair.right */
+        loop {
+            let mut q = self.stack.pop().unwrap();
+            if !q.left.is_empty() {
+                q.swap();
+            }
+            if !q.left.is_empty() {
+                return false;
+            }
+            let q_low = q.right.low.unwrap();
+            if self.lowpt[q_low] > self.lowpt[e] {
+                /* merge intervals */
+                if pair.right.is_empty() {
+                    pair.right = q.right;
+                } else {
+                    self.reference[pair.right.low.unwrap()] = q.right.high;
+                }
+                pair.right.low = q.right.low;
+            } else {
+                /* align */
+                self.reference[q_low] = self.lowpt_edge[e];
+            }
+            if self.stack.len() <= self.stack_bottom[ei] {
+                break;
+            }
+        }
+
+        /* merge conflicting return edges of the previous edges into pair.left */
+        while let Some(top) = self.stack.last() {
+            if !self.conflicting(&top.left, ei) && !self.conflicting(&top.right, ei) {
+                break;
+            }
+            let mut q = self.stack.pop().unwrap();
+            if self.conflicting(&q.right, ei) {
+                q.swap();
+            }
+            if self.conflicting(&q.right, ei) {
+                return false;
+            }
+
+            /* merge interval below lowpt(ei) into pair.right */
+            if let Some(low) = pair.right.low {
+                self.reference[low] = q.right.high;
+            }
+            if q.right.low.is_some() {
+                pair.right.low = q.right.low;
+            }
+
+            if pair.left.is_empty() {
+                pair.left = q.left;
+            } else {
+                self.reference[pair.left.low.unwrap()] = q.left.high;
+            }
+            pair.left.low = q.left.low;
+        }
+
+        if !(pair.left.is_empty() && pair.right.is_empty()) {
+            self.stack.push(pair);
+        }
+        true
+    }
+
+    fn remove_back_edges(&mut self, e: EdgeId) {
+        let u = self.src[e];
+        let height_u = self.height[u].unwrap();
+
+        /* trim back edges ending at the parent u, drop entire conflict pairs */
+        while let Some(top) = self.stack.last() {
+            if self.lowest(top) != height_u {
+                break;
+            }
+            let pair = self.stack.pop().unwrap();
+            if let Some(low) = pair.left.low {
+                self.side[low] = -1;
+            }
+        }
+
+        /* one more conflict pair to consider */
+        if let Some(mut pair) = self.stack.pop() {
+            /* trim left interval */
+            while let Some(high) = pair.left.high {
+                if self.dst[high] != u {
+                    break;
+                }
+                pair.left.high = self.reference[high];
+            }
+            if pair.left.high.is_none() {
+                if let Some(low) = pair.left.low {
+                    /* just emptied */
+                    self.reference[low] = pair.right.low;
+                    self.side[low] = -1;
+                    pair.left.low = None;
+                }
+            }
+
+            /* trim right interval */
+            while let Some(high) = pair.right.high {
+                if self.dst[high] != u {
+                    break;
+                }
+                pair.right.high = self.reference[high];
+            }
+            if pair.right.high.is_none() {
+                if let Some(low) = pair.right.low {
+                    /* just emptied */
+                    self.reference[low] = pair.left.low;
+                    self.side[low] = -1;
+                    pair.right.low = None;
+                }
+            }
+            self.stack.push(pair);
+        }
+
+        /* side of e is side of a highest return edge */
+        if self.lowpt[e] < height_u {
+            if let Some(top) = self.stack.last() {
+                let (hl, hr) = (top.left.high, top.right.high);
+                self.reference[e] = match (hl, hr) {
+                    (Some(l), None) => Some(l),
+                    (Some(l), Some(r)) if self.lowpt[l] > self.lowpt[r] => Some(l),
+                    _ => hr,
+                };
+            }
+        }
+    }
+
+    /* resolves the chain of references of an edge into its final side */
+    fn sign(&mut self, edge: EdgeId) -> isize {
+        let mut chain = vec![edge];
+        while let Some(next) = self.reference[*chain.last().unwrap()].take() {
+            chain.push(next);
+        }
+        for i in (0..chain.len() - 1).rev() {
+            self.side[chain[i]] *= self.side[chain[i + 1]];
+        }
+        self.side[edge]
+    }
+
+    fn dfs_embedding(
+        &mut self,
+        root: VertexId,
+        embedding: &mut Embedding,
+        left_ref: &mut [Option<ArcId>],
+        right_ref: &mut [Option<ArcId>],
+    ) {
+        let mut dfs_stack = vec![root];
+        'dfs: while let Some(v) = dfs_stack.pop() {
+            while self.ind[v] < self.ordered_adjs[v].len() {
+                let ei = self.ordered_adjs[v][self.ind[v]];
+                self.ind[v] += 1;
+                let w = self.dst[ei];
+                if self.parent_edge[w] == Some(ei) {
+                    /* tree edge */
+                    embedding.add_first(w, 2 * ei + 1);
+                    left_ref[v] = Some(2 * ei);
+                    right_ref[v] = Some(2 * ei);
+                    dfs_stack.push(v);
+                    dfs_stack.push(w);
+                    continue 'dfs;
+                }
+                /* back edge */
+                if self.side[ei] == 1 {
+                    embedding.add_cw(w, 2 * ei + 1, right_ref[w]);
+                } else {
+                    embedding.add_ccw(w, 2 * ei + 1, left_ref[w].unwrap());
+                    left_ref[w] = Some(2 * ei + 1);
+                }
+            }
+        }
+    }
+}
+
+/// Rotation system that is built up by the left-right test. The arcs around each vertex form a
+/// cyclic doubly linked list.
+struct Embedding {
+    cw: Vec<ArcId>,
+    ccw: Vec<ArcId>,
+    first: Vec<Option<ArcId>>,
+}
+
+impl Embedding {
+    fn new(vertex_count: usize, edge_count: usize) -> Self {
+        Embedding {
+            cw: vec![0; 2 * edge_count],
+            ccw: vec![0; 2 * edge_count],
+            first: vec![None; vertex_count],
+        }
+    }
+
+    /* adds the arc of vertex v clockwise after the reference arc */
+    fn add_cw(&mut self, v: VertexId, arc: ArcId, reference: Option<ArcId>) {
+        match reference {
+            None => {
+                self.cw[arc] = arc;
+                self.ccw[arc] = arc;
+                self.first[v] = Some(arc);
+            }
+            Some(reference) => {
+                let reference_cw = self.cw[reference];
+                self.cw[reference] = arc;
+                self.cw[arc] = reference_cw;
+                self.ccw[reference_cw] = arc;
+                self.ccw[arc] = reference;
+            }
+        }
+    }
+
+    /* adds the arc of vertex v counterclockwise before the reference arc */
+    fn add_ccw(&mut self, v: VertexId, arc: ArcId, reference: ArcId) {
+        self.add_cw(v, arc, Some(self.ccw[reference]));
+        if self.first[v] == Some(reference) {
+            self.first[v] = Some(arc);
+        }
+    }
+
+    fn add_first(&mut self, v: VertexId, arc: ArcId) {
+        match self.first[v] {
+            Some(first) => {
+                self.add_ccw(v, arc, first);
+                self.first[v] = Some(arc);
+            }
+            None => self.add_cw(v, arc, None),
+        }
+    }
+
+    fn ccw_order(&self, v: VertexId) -> Vec<ArcId> {
+        let mut order = vec![];
+        if let Some(first) = self.first[v] {
+            let mut arc = first;
+            loop {
+                order.push(arc);
+                arc = self.ccw[arc];
+                if arc == first {
+                    break;
+                }
+            }
+        }
+        order
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{is_planar, planar_embedding, KuratowskiSubgraph};
+    use crate::graph::builder::validation::validate_embedding;
+    use crate::graph::DcelBuilder;
+    use crate::read_graph_file_into_arc_list;
+
+    fn complete_graph(n: usize) -> Vec<(usize, usize)> {
+        (0..n)
+            .flat_map(|u| (u + 1..n).map(move |v| (u, v)))
+            .collect()
+    }
+
+    fn assert_valid_embedding(vertex_count: usize, edges: &[(usize, usize)]) {
+        let arc_list = planar_embedding(vertex_count, edges).unwrap();
+        assert_eq!(arc_list.arcs.len(), 2 * edges.len());
+        let builder = DcelBuilder::from(&arc_list);
+        assert_eq!(validate_embedding(&builder), Ok(()));
+    }
+
+    #[test]
+    fn planar() {
+        assert_valid_embedding(4, &complete_graph(4));
+
+        /* 5x5 grid with diagonals */
+        let mut grid = vec![];
+        for x in 0..5 {
+            for y in 0..5 {
+                let v = 5 * x + y;
+                if x < 4 {
+                    grid.push((v, v + 5));
+                }
+                if y < 4 {
+                    grid.push((v, v + 1));
+                }
+                if x < 4 && y < 4 {
+                    grid.push((v, v + 6));
+                }
+            }
+        }
+        assert_valid_embedding(25, &grid);
+
+        /* two components */
+        assert_valid_embedding(7, &[(0, 1), (1, 2), (2, 0), (3, 4), (4, 5), (5, 6), (6, 3)]);
+    }
+
+    #[test]
+    fn graph_files() {
+        for file in ["data/bsp1.graph", "data/bsp3.graph", "data/simple.graph"] {
+            let arc_list = read_graph_file_into_arc_list(file).unwrap();
+            /* forget the rotation system and shuffle the edges */
+            let mut edges: Vec<(usize, usize)> = arc_list
+                .arcs
+                .iter()
+                .filter(|(u, v)| u < v)
+                .copied()
+                .collect();
+            edges.sort_by_key(|&(u, v)| (u * 7919 + v * 104729) % 1009);
+            assert_valid_embedding(arc_list.vertex_count, &edges);
+        }
+    }
+
+    #[test]
+    fn not_planar() {
+        let k5 = planar_embedding(5, &complete_graph(5)).unwrap_err();
+        assert_eq!(k5.witness.as_ref().map(Vec::len), Some(10));
+        assert_eq!(k5.kind(), Some(KuratowskiSubgraph::K5));
+
+        let k33: Vec<(usize, usize)> = (0..3).flat_map(|u| (3..6).map(move |v| (u, v))).collect();
+        let error = planar_embedding(6, &k33).unwrap_err();
+        assert_eq!(error.witness.as_ref().map(Vec::len), Some(9));
+        assert_eq!(error.kind(), Some(KuratowskiSubgraph::K33));
+
+        /* the petersen graph contains a subdivision of K3,3 */
+        let mut petersen = vec![];
+        for i in 0..5 {
+            petersen.push((i, (i + 1) % 5));
+            petersen.push((i, i + 5));
+            petersen.push((i + 5, (i + 2) % 5 + 5));
+        }
+        let error = planar_embedding(10, &petersen).unwrap_err();
+        assert_eq!(error.kind(), Some(KuratowskiSubgraph::K33));
+        let witness = error.witness.unwrap();
+        assert!(!is_planar(10, &witness));
+        for skipped in 0..witness.len() {
+            let mut witness = witness.clone();
+            witness.remove(skipped);
+            assert!(is_planar(10, &witness));
+        }
+
+        /* a K5 in the middle of a big grid is found without testing the grid edge by edge */
+        let n = 100;
+        let mut grid = vec![];
+        for v in 0..n * n {
+            if v % n < n - 1 {
+                grid.push((v, v + 1));
+            }
+            if v < n * (n - 1) {
+                grid.push((v, v + n));
+            }
+        }
+        let k5 = complete_graph(5)
+            .into_iter()
+            .map(|(u, v)| (u + n * n, v + n * n));
+        grid.splice(grid.len() / 2..grid.len() / 2, k5);
+        let error = planar_embedding(n * n + 5, &grid).unwrap_err();
+        assert_eq!(error.witness.map(|witness| witness.len()), Some(10));
+    }
+
+    #[test]
+    fn ignores_loops_and_parallel_edges() {
+        assert_valid_embedding(3, &[(0, 1), (1, 2), (2, 0)]);
+        let arc_list = planar_embedding(3, &[(0, 1), (1, 0), (1, 1), (1, 2)]).unwrap();
+        assert_eq!(arc_list.arcs.len(), 4);
+    }
+}
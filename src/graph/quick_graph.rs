@@ -1,8 +1,9 @@
 use crate::graph::reducible::Reducible;
+use crate::graph::DcelBuilder;
 use std::collections::{HashSet};
 use std::ptr::null;
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct QuickGraph {
     pub adjacency: Vec<Option<Vec<usize>>>,
     pub edge_count: usize
@@ -86,6 +87,20 @@ impl QuickGraph {
     }
 }
 
+impl From<&DcelBuilder> for QuickGraph {
+    /* creates a graph with the adjacencies of the embedding */
+    fn from(dcel_builder: &DcelBuilder) -> Self {
+        let mut graph = QuickGraph::new(dcel_builder.vertex_count());
+        for vertex in 0..dcel_builder.vertex_count() {
+            graph.adjacency[vertex] = Some(dcel_builder.get_neighborhood(vertex));
+        }
+        graph.edge_count = graph.adjacency.iter()
+            .map(|neighborhood| neighborhood.as_ref().map_or(0, |n| n.len()))
+            .sum::<usize>() / 2;
+        graph
+    }
+}
+
 impl Reducible for QuickGraph {
      fn remove_vertex(&mut self, u: usize) {
          /* look for vertex in adjacency list */
@@ -6,7 +6,9 @@ use crate::graph::reducible::Reducible;
 use crate::graph::reductions::isolated_clique_reduction::{do_isolated_clique_reductions, IsolatedClique, transfer_isolated_clique};
 use crate::graph::reductions::nodal_fold_reduction::{do_nodal_fold_reductions, NodalFold, transfer_nodal_fold_reductions};
 use crate::graph::reductions::twin_reduction::{do_twin_reductions, transfer_twin_reductions, TwinReduction};
+use crate::Reduction;
 
+pub mod donut_reduction;
 pub mod nodal_fold_reduction;
 pub mod isolated_clique_reduction;
 pub mod twin_reduction;
@@ -25,6 +27,37 @@ pub struct Reductions {
     pub twins: Vec<TwinReduction>
 }
 
+impl Reductions {
+    /* returns the name of each requested reduction and how often it was applied */
+    pub fn applied(&self, requested: &[Reduction]) -> Vec<(String, usize)> {
+        requested.iter().map(|reduction| {
+            let count = match reduction {
+                Reduction::NodalFold => self.nodal_folds.len(),
+                Reduction::IsolatedClique => self.isolated_cliques.len(),
+                Reduction::Twin => self.twins.len(),
+            };
+            (reduction.name().to_string(), count)
+        }).collect()
+    }
+
+    /* restores the solution by transferring it through the requested reductions in reverse order */
+    pub fn transfer(&mut self, requested: &[Reduction], independence_set: &mut Vec<VertexId>) {
+        for reduction in requested.iter().rev() {
+            match reduction {
+                Reduction::NodalFold => {
+                    transfer_nodal_fold_reductions(independence_set, &mut self.nodal_folds);
+                }
+                Reduction::IsolatedClique => {
+                    transfer_isolated_clique(independence_set, &self.isolated_cliques);
+                }
+                Reduction::Twin => {
+                    transfer_twin_reductions(independence_set, &mut self.twins)
+                }
+            }
+        }
+    }
+}
+
 
 
 pub fn update_vertex_indices(
@@ -0,0 +1,307 @@
+/* reduces the graph of a single donut and decomposes the reduced graph */
+use std::collections::HashMap;
+
+use arboretum_td::graph::{HashMapGraph, MutableGraph};
+use arboretum_td::solver::Solver;
+use arboretum_td::tree_decomposition::TreeDecomposition;
+use fxhash::FxHashSet;
+
+use crate::graph::approximated_td::{ApproximatedTD, TDBuilder};
+use crate::graph::dcel::vertex::VertexId;
+use crate::graph::mis_finder::Weight;
+use crate::graph::quick_graph::QuickGraph;
+use crate::graph::reductions::isolated_clique_reduction::do_isolated_clique_reductions;
+use crate::graph::reductions::nodal_fold_reduction::do_nodal_fold_reductions;
+use crate::graph::reductions::twin_reduction::do_twin_reductions;
+use crate::graph::reductions::{ApplicableReduction, Reductions};
+use crate::graph::sub_dcel::SubDcel;
+use crate::graph::tree_decomposition::relabeled_tree_decomposition;
+use crate::graph::{Dcel, DcelBuilder};
+use crate::Reduction;
+
+/// The graph of a donut after the reductions were applied.
+///
+/// The vertices of the donut get local indices, the reductions keep these indices stable.
+/// The reductions are applied to the embedding of the donut as well, so the approximated tree
+/// decomposition is built for the reduced graph and profits from the removed vertices.
+pub struct ReducedDonut {
+    /* global index of each local vertex */
+    vertices: Vec<VertexId>,
+    graph: QuickGraph,
+    reductions: Reductions,
+    requested: Vec<Reduction>,
+}
+
+impl ReducedDonut {
+    /// Applies the requested reductions, in the given order, to the subgraph of `dcel` that is
+    /// induced by `vertices`.
+    pub fn new(dcel: &Dcel, vertices: Vec<VertexId>, requested: &[Reduction]) -> Self {
+        let local: HashMap<VertexId, VertexId> = vertices
+            .iter()
+            .enumerate()
+            .map(|(local, &global)| (global, local))
+            .collect();
+
+        /* only edges inside the donut are relevant for its independent set */
+        let mut graph = QuickGraph::new(vertices.len());
+        for (u, &global) in vertices.iter().enumerate() {
+            let mut neighborhood: Vec<VertexId> = dcel
+                .neighbors(global)
+                .into_iter()
+                .filter_map(|n| local.get(&n).copied())
+                .collect();
+            neighborhood.sort_unstable();
+            neighborhood.dedup();
+            graph.edge_count += neighborhood.len();
+            graph.adjacency[u] = Some(neighborhood);
+        }
+        graph.edge_count /= 2;
+
+        let mut reductions = Reductions::default();
+        for reduction in requested {
+            match reduction {
+                Reduction::NodalFold => {
+                    reductions.nodal_folds = do_nodal_fold_reductions(&mut graph);
+                }
+                Reduction::IsolatedClique => {
+                    reductions.isolated_cliques = do_isolated_clique_reductions(&mut graph);
+                }
+                Reduction::Twin => {
+                    reductions.twins = do_twin_reductions(&mut graph);
+                }
+            }
+        }
+
+        ReducedDonut {
+            vertices,
+            graph,
+            reductions,
+            requested: requested.to_vec(),
+        }
+    }
+
+    /// Returns the adjacency matrix of the reduced graph in local indices.
+    pub fn adjacency_matrix(&self) -> Vec<Vec<bool>> {
+        let mut matrix = vec![vec![false; self.vertices.len()]; self.vertices.len()];
+        for (u, neighborhood) in self.graph.adjacency.iter().enumerate() {
+            if let Some(neighborhood) = neighborhood {
+                neighborhood.iter().for_each(|&v| matrix[u][v] = true);
+            }
+        }
+        matrix
+    }
+
+    /// Returns the weights of the local vertices.
+    pub fn weights(&self, weights: &[Weight]) -> Vec<Weight> {
+        self.vertices.iter().map(|&v| weights[v]).collect()
+    }
+
+    /// Creates an approximated tree decomposition of the reduced graph from the embedding of
+    /// `donut`, or `None` if the reductions removed every vertex. The reductions are applied to
+    /// the embedding without its triangulation, and the tree decomposition is built from a
+    /// spanning tree of the reduced embedding that starts at `root`, the root of the spanning
+    /// tree of the whole graph. It is the fake root of the donut or one of its vertices.
+    pub fn tree_decomposition(&self, donut: &SubDcel, root: VertexId) -> Option<TreeDecomposition> {
+        let n = self.vertices.len();
+        if self.graph.adjacency.iter().all(Option::is_none) {
+            return None;
+        }
+
+        /* the fake root is the only vertex of the donut without a local index, it gets index n */
+        let local: HashMap<VertexId, VertexId> = self
+            .vertices
+            .iter()
+            .enumerate()
+            .map(|(local, &global)| (global, local))
+            .collect();
+        let index = |global: VertexId| local.get(&global).copied().unwrap_or(n);
+        let sub = &donut.sub;
+        let original_arcs = match sub.pre_triangulation_arc_count() {
+            0 => sub.num_arcs(),
+            count => count,
+        };
+        let mut builder = DcelBuilder::with_vertex_count(n + donut.fake_root().is_some() as usize);
+        for v in 0..sub.num_vertices() {
+            for &arc in sub
+                .vertex(v)
+                .arcs()
+                .iter()
+                .filter(|&&arc| arc < original_arcs)
+            {
+                let arc = sub.arc(arc);
+                builder.push_arc(
+                    index(donut.vertex_mapping[arc.src()]),
+                    index(donut.vertex_mapping[arc.dst()]),
+                );
+            }
+        }
+
+        let mut vertex_ids: HashMap<VertexId, VertexId> =
+            (0..builder.num_vertices()).map(|v| (v, v)).collect();
+        self.reduce_embedding(&mut builder, &mut vertex_ids);
+        if builder.num_vertices() == 1 {
+            let mut td = TreeDecomposition {
+                bags: vec![],
+                root: None,
+                max_bag_size: 0,
+            };
+            let vertex = vertex_ids.keys().copied().find(|&v| v < n)?;
+            td.add_bag(FxHashSet::from_iter([vertex]));
+            return Some(td);
+        }
+
+        /* the reductions may split the donut, so its parts are connected to the root */
+        let root = vertex_ids.get(&index(root)).copied().unwrap_or(0);
+        for component in builder.clone().build().connected_components() {
+            if !component.contains(&root) {
+                builder.push_arc(root, component[0]);
+                builder.push_arc(component[0], root);
+            }
+        }
+        let mut graph = builder.build();
+        graph.triangulate();
+        let spanning_tree = graph.spanning_tree(root);
+        let mut td_builder = TDBuilder::new(&spanning_tree);
+        let td = ApproximatedTD::from(&mut td_builder);
+
+        let mut to_local = vec![None; graph.num_vertices()];
+        for (&local, &id) in vertex_ids.iter().filter(|(&local, _)| local < n) {
+            to_local[id] = Some(local);
+        }
+        relabeled_tree_decomposition(&td, |v| to_local[v])
+    }
+
+    /* applies the reductions of the reduced graph to the embedding, in the same order */
+    fn reduce_embedding(
+        &self,
+        builder: &mut DcelBuilder,
+        vertex_ids: &mut HashMap<VertexId, VertexId>,
+    ) {
+        for reduction in &self.requested {
+            match reduction {
+                Reduction::NodalFold => self
+                    .reductions
+                    .nodal_folds
+                    .iter()
+                    .for_each(|nodal_fold| nodal_fold.reduce_dcel_builder(builder, vertex_ids)),
+                Reduction::IsolatedClique => self
+                    .reductions
+                    .isolated_cliques
+                    .iter()
+                    .for_each(|clique| clique.reduce_dcel_builder(builder, vertex_ids)),
+                Reduction::Twin => self
+                    .reductions
+                    .twins
+                    .iter()
+                    .for_each(|twin| twin.reduce_dcel_builder(builder, vertex_ids)),
+            }
+        }
+    }
+
+    /// Computes a tree decomposition of the reduced graph with the solvers of arboretum, or
+    /// `None` if the reductions removed every vertex.
+    /// Small atoms of the graph are decomposed exactly, larger ones heuristically.
+    pub fn exact_tree_decomposition(&self) -> Option<TreeDecomposition> {
+        let graph = self.hash_map_graph();
+        let td = Solver::auto(&graph).solve(&graph);
+        (!td.bags.is_empty()).then_some(td)
+    }
+
+    /* the reduced graph as input for arboretum */
+    fn hash_map_graph(&self) -> HashMapGraph {
+        let mut graph = HashMapGraph::new();
+        for (u, neighborhood) in self.graph.adjacency.iter().enumerate() {
+            if let Some(neighborhood) = neighborhood {
+                graph.add_vertex(u);
+                neighborhood.iter().for_each(|&v| graph.add_edge(u, v));
+            }
+        }
+        graph
+    }
+
+    /// Lifts an independent set of the reduced graph in local indices to an independent set of
+    /// the whole graph in global indices.
+    pub fn transfer(mut self, mut independence_set: Vec<VertexId>) -> Vec<VertexId> {
+        self.reductions
+            .transfer(&self.requested, &mut independence_set);
+        independence_set
+            .into_iter()
+            .map(|v| self.vertices[v])
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::ReducedDonut;
+    use crate::graph::mis_finder::{find_connected_vertices, find_mis, find_mis_exhaustive};
+    use crate::graph::nice_tree_decomp::NiceTreeDecomposition;
+    use crate::graph::node_relations::NodeRelations;
+    use crate::read_graph_file_into_dcel_builder;
+    use crate::Reduction;
+    use arboretum_td::tree_decomposition::TreeDecomposition;
+    use std::collections::HashSet;
+
+    /* solves the reduced donut with the given decomposition and returns the lifted set */
+    fn solve(reduced: ReducedDonut, decomp: Option<TreeDecomposition>) -> Vec<usize> {
+        let mis = match decomp {
+            Some(decomp) => {
+                assert!(decomp.verify(&reduced.hash_map_graph()).is_ok());
+                let ntd = NiceTreeDecomposition::from(&decomp);
+                assert!(ntd.validate(&decomp, &NodeRelations::new(&ntd.td)));
+                let (mis, _) = find_mis(&reduced.adjacency_matrix(), &ntd).unwrap();
+                mis.into_iter().collect()
+            }
+            None => vec![],
+        };
+        reduced.transfer(mis)
+    }
+
+    #[test]
+    fn reduced_donuts() {
+        let graph = read_graph_file_into_dcel_builder("data/bsp3.graph")
+            .unwrap()
+            .build();
+        let spanning_tree = graph.spanning_tree(0);
+        let requested = [
+            Reduction::Twin,
+            Reduction::NodalFold,
+            Reduction::IsolatedClique,
+        ];
+        let mut applied = 0;
+        for donut in (0..=2).flat_map(|i| graph.find_donuts_for_k(2, i, &spanning_tree).unwrap()) {
+            let td = donut.tree_decomposition(spanning_tree.root()).unwrap();
+
+            let reduced = ReducedDonut::new(&graph, donut.original_vertices(), &requested);
+            applied += reduced
+                .reductions
+                .applied(&requested)
+                .iter()
+                .map(|(_, count)| count)
+                .sum::<usize>();
+            let decomp = reduced.tree_decomposition(&donut, spanning_tree.root());
+            /* the reduced embedding has fewer vertices, so its bags are not larger */
+            assert!(decomp.as_ref().map_or(0, |decomp| decomp.max_bag_size) <= td.max_bag_size);
+            let mis = solve(reduced, decomp);
+            let reduced = ReducedDonut::new(&graph, donut.original_vertices(), &requested);
+            let decomp = reduced.exact_tree_decomposition();
+            let exact_mis = solve(reduced, decomp);
+            assert_eq!(mis.len(), exact_mis.len());
+
+            /* the reductions must not change the size of the MIS of the unreduced donut */
+            let vertices = donut.original_vertices();
+            let adjacency_matrix = graph.adjacency_matrix();
+            let donut_matrix: Vec<Vec<bool>> = vertices
+                .iter()
+                .map(|&u| vertices.iter().map(|&v| adjacency_matrix[u][v]).collect())
+                .collect();
+            let (unreduced_mis, _) = find_mis_exhaustive(&donut_matrix).unwrap();
+            assert_eq!(mis.len(), unreduced_mis.len());
+
+            let unique: HashSet<usize> = mis.iter().copied().collect();
+            assert_eq!(unique.len(), mis.len());
+            assert!(find_connected_vertices(&unique, &graph.adjacency_matrix()).is_empty());
+        }
+        assert!(applied > 0);
+    }
+}
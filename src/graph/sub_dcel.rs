@@ -1,5 +1,10 @@
 use std::error::Error;
 
+use arboretum_td::tree_decomposition::TreeDecomposition;
+use fxhash::FxHashSet;
+
+use super::approximated_td::{ApproximatedTD, TDBuilder};
+use super::tree_decomposition::relabeled_tree_decomposition;
 use super::{
     dcel::{
         arc,
@@ -76,6 +81,41 @@ impl SubDcel {
     pub fn fake_root(&self) -> Option<VertexId> {
         self.fake_root
     }
+
+    /// Returns the original indices of all vertices of the donut without its fake root.
+    pub fn original_vertices(&self) -> Vec<VertexId> {
+        self.vertex_mapping
+            .iter()
+            .copied()
+            .filter(|&v| Some(v) != self.fake_root)
+            .collect()
+    }
+
+    /// Creates a tree decomposition of the triangulated donut with the original indices. Its
+    /// spanning tree starts at the fake root, or else at `root`, the root of the spanning tree of
+    /// the whole graph, so the donut is decomposed like a graph of its own levels. The fake root
+    /// is removed from the bags.
+    pub fn tree_decomposition(&self, root: VertexId) -> Option<TreeDecomposition> {
+        if self.sub.num_faces() == 0 {
+            /* a single vertex has no arcs and therefore no faces */
+            let mut td = TreeDecomposition {
+                bags: vec![],
+                root: None,
+                max_bag_size: 0,
+            };
+            let vertices = self.original_vertices();
+            (!vertices.is_empty()).then(|| td.add_bag(FxHashSet::from_iter(vertices)));
+            return td.root.map(|_| td);
+        }
+        let start = self.get_local_index(self.fake_root.unwrap_or(root));
+        let spanning_tree = self.sub.spanning_tree(start.unwrap_or(0));
+        let mut builder = TDBuilder::new(&spanning_tree);
+        let td = ApproximatedTD::from(&mut builder);
+        relabeled_tree_decomposition(&td, |v| {
+            let original = self.vertex_mapping[v];
+            (Some(original) != self.fake_root).then_some(original)
+        })
+    }
 }
 
 #[derive(Debug)]
@@ -1,6 +1,8 @@
+use std::collections::VecDeque;
 use std::{fs::File, io::Error, io::Write, process::Command};
 
 use crate::graph::approximated_td::ApproximatedTD;
+use crate::graph::dcel::vertex::VertexId;
 use arboretum_td::tree_decomposition::TreeDecomposition;
 use fxhash::FxHashSet;
 
@@ -40,6 +42,60 @@ impl From<&ApproximatedTD<'_>> for TreeDecomposition {
     }
 }
 
+/// Creates a tree decomposition with the bags of `td`, in which every vertex `v` is replaced by
+/// `label(v)` or dropped if it is `None`. Bags that become empty are skipped, or `None` is
+/// returned if all of them do.
+pub fn relabeled_tree_decomposition(
+    td: &ApproximatedTD,
+    label: impl Fn(VertexId) -> Option<VertexId>,
+) -> Option<TreeDecomposition> {
+    let bags: Vec<FxHashSet<VertexId>> = td
+        .bags()
+        .iter()
+        .map(|bag| bag.iter().filter_map(|&v| label(v)).collect())
+        .collect();
+
+    /* root the tree to get an order in which children come after their parents */
+    let mut parent: Vec<Option<usize>> = vec![None; bags.len()];
+    let mut order = Vec::with_capacity(bags.len());
+    let mut visited = vec![false; bags.len()];
+    let mut queue = VecDeque::from([td.root_bag()]);
+    visited[td.root_bag()] = true;
+    while let Some(bag) = queue.pop_front() {
+        order.push(bag);
+        for &neighbor in td.neighbours(bag) {
+            if !visited[neighbor] {
+                visited[neighbor] = true;
+                parent[neighbor] = Some(bag);
+                queue.push_back(neighbor);
+            }
+        }
+    }
+
+    /* skip bags that became empty by attaching their children to the next ancestor */
+    let mut nearest: Vec<Option<usize>> = vec![None; bags.len()];
+    let mut result = TreeDecomposition {
+        bags: vec![],
+        root: None,
+        max_bag_size: 0,
+    };
+    for &bag in &order {
+        let ancestor = parent[bag].and_then(|p| nearest[p]);
+        if bags[bag].is_empty() {
+            nearest[bag] = ancestor;
+            continue;
+        }
+        let id = result.add_bag(bags[bag].clone());
+        nearest[bag] = Some(id);
+        /* subtrees below empty bags share no vertices, so they can hang below any bag */
+        if let Some(ancestor) = ancestor.or(result.root.filter(|&root| root != id)) {
+            result.add_edge(ancestor, id);
+        }
+    }
+
+    result.root.map(|_| result)
+}
+
 /// Writes a tree decomposition to a given dot file.
 pub fn td_write_to_dot(
     title: &str,
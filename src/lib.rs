@@ -0,0 +1,2413 @@
+use std::collections::{HashMap, HashSet};
+use std::error::Error;
+use std::ops::{Range, RangeInclusive};
+use std::path::Path;
+use std::time::{Duration, Instant};
+pub mod graph;
+
+#[macro_use]
+pub mod logger;
+
+pub use graph::builder::validation::{validate_embedding, EmbeddingIssue, InvalidEmbedding};
+pub use graph::planarity::{
+    is_planar, planar_embedding, KuratowskiSubgraph, NotPlanar, WITNESS_BUDGET,
+};
+use rayon::prelude::*;
+use rayon::{ThreadPool, ThreadPoolBuildError, ThreadPoolBuilder};
+
+use graph::branch_and_reduce::{clique_cover, clique_cover_bound, find_mis_branch_and_reduce};
+use graph::dcel::spanning_tree::{min_eccentricity_vertex, pseudo_diameter_center, SpanningTree};
+use graph::dcel::vertex::VertexId;
+use graph::dcel_file_writer::{JsDataWriter, JsonResultWriter};
+use graph::dominating_set::{
+    find_dominating_set, find_overlapping_donuts, find_undominated_vertex, OverlappingDonut,
+};
+use graph::dyn_table::dt_dense::table_bytes;
+pub use graph::graph_file_reader::{
+    read_graph_file, read_graph_file_into_arc_list, read_graph_file_into_dcel_builder,
+    read_graph_file_into_quick_graph, ArcList, EdgeList, GraphFormat, GraphParseError,
+};
+pub use graph::graph_file_writer::write_graph_file;
+use graph::heuristics::{find_greedy_mis, improve_mis};
+
+pub use graph::mis_finder::Weight;
+use graph::mis_finder::{find_mis_weight_fast, find_weighted_mis_fast, FindMisError};
+use graph::nice_tree_decomp::NiceTreeDecomposition;
+
+use graph::quick_graph::QuickGraph;
+use graph::sub_dcel::SubDcel;
+use graph::{Dcel, DcelBuilder};
+
+use crate::graph::mis_finder::find_connected_vertices;
+use crate::graph::node_relations::NodeRelations;
+use crate::graph::reductions::donut_reduction::ReducedDonut;
+use crate::graph::reductions::isolated_clique_reduction::do_isolated_clique_reductions;
+use crate::graph::reductions::nodal_fold_reduction::do_nodal_fold_reductions;
+use crate::graph::reductions::twin_reduction::do_twin_reductions;
+use crate::graph::reductions::{ApplicableReduction, Reductions};
+#[cfg(feature = "logging")]
+use crate::graph::tree_decomposition::td_write_to_pdf;
+
+/// All representations of an input graph that are needed to solve it and to write the results.
+pub struct LoadedGraph {
+    /// The arcs as they were read, before any reduction is applied.
+    pub arc_list: ArcList,
+    pub dcel_builder: DcelBuilder,
+    pub quick_graph: QuickGraph,
+}
+
+impl LoadedGraph {
+    /// Same as [`solve_graph`], but also returns the untouched embedding of the input graph,
+    /// e.g. for [`write_web_file`]. The embedding is validated only once for both.
+    pub fn solve(self, config: SolverConfig) -> Result<(Dcel, MISResult), SolveError> {
+        let warnings = embedding_warnings(&self.dcel_builder)?;
+        let original = self.dcel_builder.clone().build();
+        let result = solve_valid_graph(self.dcel_builder, self.quick_graph, config, warnings)?;
+        Ok((original, result))
+    }
+}
+
+/// Reads the graph file once and derives all representations of the graph from it.
+/// Graphs of formats without a rotation system are embedded while they are read.
+pub fn load_graph<P: AsRef<Path>>(
+    filename: P,
+    format: GraphFormat,
+) -> Result<LoadedGraph, GraphParseError> {
+    let arc_list = read_graph_file(filename, format)?;
+    let dcel_builder = DcelBuilder::from(&arc_list);
+    let quick_graph = QuickGraph::from(&arc_list);
+    Ok(LoadedGraph {
+        arc_list,
+        dcel_builder,
+        quick_graph,
+    })
+}
+
+/// Writes the graph, the result and its donuts for the web viewer.
+/// Fails for a graph without vertices, which has no spanning tree to show.
+pub fn write_web_file(
+    filename: &str,
+    dcel: &Dcel,
+    result: MISResult,
+) -> Result<(), Box<dyn Error>> {
+    if dcel.num_vertices() == 0 {
+        return Err("The web file cannot show a graph without vertices".into());
+    }
+    let mut writer = JsDataWriter::new(filename, dcel, result)?;
+    writer.write_data()
+}
+
+/// Writes the result, its timings and the statistics of the graph as JSON.
+pub fn write_result_json<P: AsRef<Path>>(filename: P, result: &MISResult) -> std::io::Result<()> {
+    JsonResultWriter::new(result).write_to(filename)
+}
+
+#[derive(Debug, Clone, clap::ValueEnum)]
+pub enum Reduction {
+    Twin,
+    IsolatedClique,
+    NodalFold,
+}
+
+/// The problem that is solved.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, clap::ValueEnum)]
+pub enum Problem {
+    #[default]
+    IndependentSet,
+    /// The complement of the independent set, which covers every edge.
+    VertexCover,
+    /// A minimum dominating set, which is found by its own dynamic program.
+    DominatingSet,
+}
+
+impl Problem {
+    /// Returns the name of the problem as it is used on the command line.
+    pub fn name(&self) -> &'static str {
+        match self {
+            Problem::IndependentSet => "independent-set",
+            Problem::VertexCover => "vertex-cover",
+            Problem::DominatingSet => "dominating-set",
+        }
+    }
+}
+
+/// How the root of the spanning tree is chosen. The levels of the breadth-first search from the
+/// root define the donuts of the PTAS, so a root with fewer levels gives thinner donuts and
+/// smaller bags.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum RootSelection {
+    /// Always the first vertex, the generated circular graphs have their center there.
+    #[default]
+    First,
+    /// The middle of a long shortest path, see [pseudo_diameter_center].
+    Center,
+    /// The vertex with the fewest levels among the center and some samples, see
+    /// [min_eccentricity_vertex]. This needs a breadth-first search for every sample.
+    Eccentricity,
+    /// The given vertex of the input graph.
+    Vertex(VertexId),
+}
+
+static LOG: &str = "logs/solver_out.txt";
+
+/* number of vertices that are tried by RootSelection::Eccentricity */
+const ROOT_SAMPLES: usize = 16;
+
+pub struct PTASConfig {
+    pub k: usize,
+    pub exact_donut_tree_decomposition: bool,
+    pub reduce_input: Vec<Reduction>,
+    pub reduce_donuts: Vec<Reduction>,
+}
+
+impl PTASConfig {
+    /// Returns the smallest `k` whose guarantee `k / (k + 1)` is at least `1 - epsilon`, i.e. the
+    /// set is within `epsilon` of the maximum. `epsilon` has to be between 0 and 1, a `k` beyond
+    /// [usize::MAX] saturates, which solves the whole graph like any `k` above its levels.
+    pub fn k_for_epsilon(epsilon: f64) -> Option<usize> {
+        if !(epsilon > 0.0 && epsilon < 1.0) {
+            return None;
+        }
+
+        /* k is enough iff (k + 1) * epsilon >= 1, which mul_add decides without rounding */
+        let enough = |k: f64| (k + 1.0).mul_add(epsilon, -1.0) >= 0.0;
+        let mut k = ((1.0 - epsilon) / epsilon).ceil().max(1.0);
+        /* the rounded quotient is off by at most one, beyond 2^53 the steps are lost anyway */
+        if k < (1u64 << 53) as f64 {
+            if k > 1.0 && enough(k - 1.0) {
+                k -= 1.0;
+            } else if !enough(k) {
+                k += 1.0;
+            }
+        }
+        Some(k as usize)
+    }
+}
+
+pub enum Scheme {
+    PTAS { config: PTASConfig },
+    AllWithTD,
+    Exhaustive { reduce_input: Vec<Reduction> },
+    Heuristic,
+}
+
+impl Scheme {
+    /// Returns the name of the scheme as it is used on the command line.
+    pub fn name(&self) -> &'static str {
+        match self {
+            Scheme::PTAS { .. } => "ptas",
+            Scheme::AllWithTD => "all-with-td",
+            Scheme::Exhaustive { .. } => "exhaustive",
+            Scheme::Heuristic => "heuristic",
+        }
+    }
+
+    /// Returns the reductions that are applied to the input graph before it is solved.
+    pub fn input_reductions(&self) -> &[Reduction] {
+        match self {
+            Scheme::PTAS { config } => &config.reduce_input,
+            Scheme::AllWithTD | Scheme::Heuristic => &[],
+            Scheme::Exhaustive { reduce_input } => reduce_input,
+        }
+    }
+}
+
+impl Reduction {
+    /// Returns the name of the reduction as it is used on the command line.
+    pub fn name(&self) -> &'static str {
+        match self {
+            Reduction::Twin => "twin",
+            Reduction::IsolatedClique => "isolated-clique",
+            Reduction::NodalFold => "nodal-fold",
+        }
+    }
+}
+
+/// Size of the input graph.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct GraphStats {
+    pub vertices: usize,
+    pub edges: usize,
+    pub faces: usize,
+}
+
+impl From<&Dcel> for GraphStats {
+    fn from(graph: &Dcel) -> Self {
+        GraphStats {
+            vertices: graph.num_vertices(),
+            edges: graph.num_arcs() / 2,
+            faces: graph.num_faces(),
+        }
+    }
+}
+
+/// A vertex cover that is the complement of the independent set of a [MISResult].
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct VertexCover {
+    pub vertices: Vec<VertexId>,
+    /// Total weight of the cover, which is its size if the graph has no vertex weights.
+    pub weight: Weight,
+    /// No vertex cover of the graph has a smaller weight than this.
+    /// The PTAS finds an independent set with at least `k / (k + 1)` of the maximum weight, the
+    /// exact schemes find the maximum, which bounds the weight of the minimum vertex cover.
+    pub lower_bound: Weight,
+}
+
+#[derive(Debug, Default)]
+pub struct MISResult {
+    pub problem: Problem,
+    pub scheme: String,
+    pub timings: Vec<(String, Duration)>,
+    pub total_time: Duration,
+    pub result: Vec<VertexId>,
+    /// Total weight of the set, which is its size if the graph has no vertex weights.
+    pub weight: Weight,
+    /// The set weighs at least `guarantee` times the maximum, which is `k / (k + 1)` for the PTAS,
+    /// 1 for the exact schemes and 0 for the heuristic. A dominating set of the PTAS weighs at
+    /// most `1 + 2 / (k + 1)` times the minimum.
+    pub guarantee: f64,
+    /// The maximum independent set weighs at most this, a minimum dominating set at least this.
+    /// For the PTAS, this is the best bound of all offsets: the sets of the donuts are optimal,
+    /// so the maximum is at most their weight plus a clique cover bound of the deleted levels.
+    pub optimum_bound: Weight,
+    pub k: usize,
+    pub i: usize,
+    /// The root of the spanning tree in the input graph, see [SolverConfig::with_root].
+    /// Every connected component has its own spanning tree, `root` and `i` belong to the
+    /// component of a given root or else to the largest component.
+    pub root: VertexId,
+    /// The number of levels below the root of the spanning tree, the most of all components.
+    pub max_level: usize,
+    /// The set was found on the donuts of [MISResult::k], [MISResult::i] and [MISResult::root],
+    /// which the web file shows. Only the independent set PTAS and the exact scheme with tree
+    /// decompositions have donuts, and only if the reductions left some levels.
+    pub has_donuts: bool,
+    /// A limit of [SolverConfig::with_time_limit] or [SolverConfig::with_memory_limit] was hit,
+    /// or the dynamic program of a donut failed, see [MISResult::warnings], so the set is the
+    /// best of the offsets in [MISResult::offsets] or, if there are none, of the heuristic.
+    /// It has no guarantee, but [MISResult::optimum_bound] still holds.
+    pub partial: bool,
+    /// The offsets `i` of the PTAS whose donuts were all solved, in the component of
+    /// [MISResult::root].
+    pub offsets: Vec<usize>,
+    pub graph: GraphStats,
+    /// Name of each applied reduction and how often it was applied.
+    pub reductions: Vec<(String, usize)>,
+    /// The vertex cover, if [Problem::VertexCover] is solved.
+    pub cover: Option<VertexCover>,
+    /// Issues that did not stop the solver, e.g. of the embedding or the memory budget.
+    pub warnings: Vec<String>,
+}
+
+impl MISResult {
+    /// Returns the weight of the set relative to [MISResult::optimum_bound], which is the
+    /// guarantee for this instance: The set weighs at least this fraction of the maximum, or a
+    /// dominating set at most this multiple of the minimum.
+    pub fn ratio(&self) -> f64 {
+        match self.optimum_bound {
+            0 => 1.0,
+            bound => self.weight as f64 / bound as f64,
+        }
+    }
+}
+
+/// The bytes that the tables of the dynamic program may take before a warning by default, see
+/// [SolverConfig::with_memory_budget].
+pub const DEFAULT_MEMORY_BUDGET: usize = 4 << 30;
+
+/// Configuration of a [solve] call.
+pub struct SolverConfig {
+    pub scheme: Scheme,
+    /// The weight of each vertex. Without weights, the largest set is searched.
+    pub weights: Option<Vec<Weight>>,
+    pub problem: Problem,
+    /// Only computes the weight of the independent set, see [SolverConfig::with_size_only].
+    pub size_only: bool,
+    /// The number of threads of the solver, see [SolverConfig::with_threads].
+    pub threads: Option<usize>,
+    /// Computes the tables of the dynamic program in parallel, see
+    /// [SolverConfig::with_parallel_dp].
+    pub parallel_dp: bool,
+    /// Improves the set of the scheme, see [SolverConfig::with_local_search].
+    pub local_search: bool,
+    /// How the root of the spanning tree is chosen, see [SolverConfig::with_root].
+    pub root: RootSelection,
+    /// Bytes that a table of the dynamic program may take without a warning,
+    /// [DEFAULT_MEMORY_BUDGET] by default, see [SolverConfig::with_memory_budget].
+    pub memory_budget: Option<usize>,
+    /// The time after which no dynamic program is started, see [SolverConfig::with_time_limit].
+    pub time_limit: Option<Duration>,
+    /// Bytes that the largest table of a dynamic program may take, see
+    /// [SolverConfig::with_memory_limit].
+    pub memory_limit: Option<usize>,
+}
+
+impl SolverConfig {
+    pub fn new(scheme: Scheme) -> Self {
+        SolverConfig {
+            scheme,
+            weights: None,
+            problem: Problem::IndependentSet,
+            size_only: false,
+            threads: None,
+            parallel_dp: false,
+            local_search: false,
+            root: RootSelection::default(),
+            memory_budget: Some(DEFAULT_MEMORY_BUDGET),
+            time_limit: None,
+            memory_limit: None,
+        }
+    }
+
+    /// Starts no dynamic program of an independent set after `limit` has passed since the start
+    /// of the solver. A running dynamic program is finished, so the limit may be exceeded by the
+    /// time of a single donut. The result is the best offset whose donuts were all solved, or
+    /// the heuristic if there is none, and is marked as [MISResult::partial].
+    pub fn with_time_limit(mut self, limit: Duration) -> Self {
+        self.time_limit = Some(limit);
+        self
+    }
+
+    /// Skips the dynamic programs of independent sets whose largest table would take more than
+    /// `bytes`, like [SolverConfig::with_time_limit] skips them after the time limit.
+    pub fn with_memory_limit(mut self, bytes: usize) -> Self {
+        self.memory_limit = Some(bytes);
+        self
+    }
+
+    /// Warns before the dynamic program if the bags that are expected for `k`, or for the whole
+    /// graph if it has at most `k` levels, have tables of more than `bytes`. A donut of `k`
+    /// levels has bags of at most `3 k + 1` vertices, whose tables take `2^(3 k + 1)` entries.
+    /// The dynamic programs still run, use [SolverConfig::with_memory_limit] to skip them.
+    pub fn with_memory_budget(mut self, bytes: usize) -> Self {
+        self.memory_budget = Some(bytes);
+        self
+    }
+
+    /// Chooses the root of the spanning tree, whose levels define the donuts. By default, the
+    /// first vertex is used.
+    pub fn with_root(mut self, root: RootSelection) -> Self {
+        self.root = root;
+        self
+    }
+
+    /// Improves the independent set of the scheme with the local search of [improve_mis], so the
+    /// PTAS result is a warm start. The set never gets lighter, but the search takes additional
+    /// time. This cannot be combined with [SolverConfig::with_size_only].
+    pub fn with_local_search(mut self) -> Self {
+        self.local_search = true;
+        self
+    }
+
+    /// Additionally splits the subsets of large bags and independent subtrees of the nice tree
+    /// decompositions between the threads. This helps if a few wide donuts take most of the time.
+    pub fn with_parallel_dp(mut self) -> Self {
+        self.parallel_dp = true;
+        self
+    }
+
+    /// Solves the donuts of the PTAS and the parallel dynamic programs with this many threads.
+    /// Without this, as many threads as there are CPUs are used.
+    pub fn with_threads(mut self, threads: usize) -> Self {
+        self.threads = Some(threads);
+        self
+    }
+
+    /// Skips the reconstruction of the independent set, so the dynamic programs keep nothing but
+    /// the tables of the bags they still need. The result has the weight of the set but no
+    /// vertices. This only works for [Problem::IndependentSet] without reductions.
+    pub fn with_size_only(mut self) -> Self {
+        self.size_only = true;
+        self
+    }
+
+    /// Additionally derives the solution of `problem` from the independent set.
+    pub fn with_problem(mut self, problem: Problem) -> Self {
+        self.problem = problem;
+        self
+    }
+
+    /// Searches the independent set with the largest total weight instead of the largest size.
+    pub fn with_weights(mut self, weights: Vec<Weight>) -> Self {
+        self.weights = Some(weights);
+        self
+    }
+}
+
+/// Represents possible errors that can occur while solving an instance with [solve].
+#[derive(Debug)]
+pub enum SolveError {
+    /// The input is not a valid planar embedding.
+    InvalidEmbedding(InvalidEmbedding),
+    /// The dynamic program could not find a maximum independent set.
+    FindMis(FindMisError),
+    /// The donuts or tree decompositions could not be built.
+    Decomposition(Box<dyn Error>),
+    /// The two vertices of the set are adjacent, either in the set of an offset or in the
+    /// lifted set in the original graph.
+    NotIndependent(VertexId, VertexId),
+    /// The edge between the two vertices is not covered by the vertex cover.
+    NotCovered(VertexId, VertexId),
+    /// The vertex is not dominated by the dominating set.
+    NotDominated(VertexId),
+    /// The reductions only preserve maximum independent sets, so they cannot be used for the
+    /// problem.
+    UnsupportedReductions(Problem),
+    /// The number of weights does not match the number of vertices.
+    WeightCount { expected: usize, found: usize },
+    /// The reductions only preserve maximum independent sets if all vertices have the same weight.
+    WeightedReductions,
+    /// The problem or the reductions need the vertices of the set, which are not reconstructed
+    /// with [SolverConfig::with_size_only].
+    SizeOnly,
+    /// The thread pool for [SolverConfig::with_threads] could not be created.
+    ThreadPool(ThreadPoolBuildError),
+    /// The heuristics only find independent sets, so they cannot be used for the problem.
+    UnsupportedHeuristics(Problem),
+    /// The root of [RootSelection::Vertex] is not a vertex of the graph.
+    InvalidRoot(VertexId),
+    /// The `k` of [PTASConfig] is 0, but every donut needs at least one level.
+    InvalidK,
+    /// The nice tree decomposition of a donut does not match its tree decomposition.
+    InvalidNiceTreeDecomposition,
+}
+
+impl Error for SolveError {}
+
+impl std::fmt::Display for SolveError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SolveError::InvalidEmbedding(e) => write!(f, "Invalid input: {e}"),
+            SolveError::FindMis(e) => write!(f, "{e}"),
+            SolveError::Decomposition(e) => write!(f, "Could not decompose the graph: {e}"),
+            SolveError::NotIndependent(u, v) => {
+                write!(f, "The set contains the adjacent vertices {u} and {v}")
+            }
+            SolveError::NotCovered(u, v) => {
+                write!(f, "The edge {u} - {v} is not covered")
+            }
+            SolveError::NotDominated(v) => write!(f, "The vertex {v} is not dominated"),
+            SolveError::UnsupportedReductions(problem) => {
+                write!(f, "Reductions cannot be used for {}", problem.name())
+            }
+            SolveError::WeightCount { expected, found } => {
+                write!(f, "Expected {expected} vertex weights but got {found}")
+            }
+            SolveError::WeightedReductions => {
+                write!(
+                    f,
+                    "Reductions can only be applied if all vertices have the same weight"
+                )
+            }
+            SolveError::ThreadPool(e) => write!(f, "Could not create the threads: {e}"),
+            SolveError::UnsupportedHeuristics(problem) => {
+                write!(f, "Heuristics cannot be used for {}", problem.name())
+            }
+            SolveError::InvalidRoot(v) => write!(f, "The root {v} is not a vertex of the graph"),
+            SolveError::InvalidK => write!(f, "The k of the PTAS has to be at least 1"),
+            SolveError::InvalidNiceTreeDecomposition => {
+                write!(f, "The nice tree decomposition is not valid")
+            }
+            SolveError::SizeOnly => {
+                write!(
+                    f,
+                    "Only the weight of an independent set without reductions can be computed without its vertices"
+                )
+            }
+        }
+    }
+}
+
+impl From<InvalidEmbedding> for SolveError {
+    fn from(error: InvalidEmbedding) -> Self {
+        SolveError::InvalidEmbedding(error)
+    }
+}
+
+impl From<FindMisError> for SolveError {
+    fn from(error: FindMisError) -> Self {
+        SolveError::FindMis(error)
+    }
+}
+
+impl From<ThreadPoolBuildError> for SolveError {
+    fn from(error: ThreadPoolBuildError) -> Self {
+        SolveError::ThreadPool(error)
+    }
+}
+
+impl From<Box<dyn Error>> for SolveError {
+    fn from(error: Box<dyn Error>) -> Self {
+        SolveError::Decomposition(error)
+    }
+}
+
+struct Stopwatch {
+    current: String,
+    current_start: Instant,
+    timings: Vec<(String, Duration)>,
+}
+
+impl Stopwatch {
+    fn new() -> Self {
+        Self {
+            timings: vec![],
+            current: String::from(""),
+            current_start: Instant::now(),
+        }
+    }
+
+    fn start(&mut self, period: &str) {
+        self.current = String::from(period);
+        self.current_start = Instant::now();
+    }
+
+    fn stop(&mut self) {
+        let stopping_time = Instant::now();
+        let duration = stopping_time.duration_since(self.current_start);
+        self.timings.push((self.current.clone(), duration));
+    }
+
+    /* adds a period that was measured outside of start and stop */
+    fn record(&mut self, period: String, duration: Duration) {
+        self.timings.push((period, duration));
+    }
+}
+
+/* how the dynamic program of a (nice) tree decomposition is run */
+#[derive(Clone, Copy)]
+struct DpOptions {
+    /// Only the weight of the set is computed, see [SolverConfig::with_size_only].
+    size_only: bool,
+    /// See [SolverConfig::with_parallel_dp].
+    parallel: bool,
+    /// See [SolverConfig::with_memory_budget].
+    memory_budget: Option<usize>,
+    /// No dynamic program is started after this, see [SolverConfig::with_time_limit].
+    deadline: Option<Instant>,
+    /// See [SolverConfig::with_memory_limit].
+    memory_limit: Option<usize>,
+}
+
+impl DpOptions {
+    /* returns the set, which is empty for `size_only`, and its weight */
+    fn solve(
+        &self,
+        adjacency_matrix: &[Vec<bool>],
+        weights: &[Weight],
+        ntd: &NiceTreeDecomposition,
+    ) -> Result<(HashSet<VertexId>, Weight), FindMisError> {
+        match self.size_only {
+            true => find_mis_weight_fast(adjacency_matrix, weights, ntd, self.parallel)
+                .map(|weight| (HashSet::new(), weight)),
+            false => find_weighted_mis_fast(adjacency_matrix, weights, ntd, self.parallel),
+        }
+    }
+
+    /* returns which limit does not allow a dynamic program with bags of this size */
+    fn exceeded_limit(&self, bag_size: usize) -> Option<&'static str> {
+        let expired = |deadline: Instant| Instant::now() >= deadline;
+        if self.deadline.is_some_and(expired) {
+            return Some("time limit");
+        }
+        match self.memory_limit {
+            Some(limit) if table_bytes(bag_size) > limit => Some("memory limit"),
+            _ => None,
+        }
+    }
+
+    /* returns a warning if the bags of a donut with this many levels may exceed the budget */
+    fn check_memory_budget(&self, levels: usize) -> Option<String> {
+        let budget = self.memory_budget?;
+        /* the tree paths of the three corners of a face share the root of the donut */
+        let bag_size = 3 * levels + 1;
+        let bytes = table_bytes(bag_size);
+        (bytes > budget).then(|| {
+            format!(
+                "bags of {levels} levels may have {bag_size} vertices, whose tables take {bytes} \
+                 bytes, more than the memory budget of {budget} bytes"
+            )
+        })
+    }
+}
+
+/* runs `op` on the thread pool of [SolverConfig::with_threads], or on the global one */
+fn in_pool<R: Send>(pool: Option<&ThreadPool>, op: impl FnOnce() -> R + Send) -> R {
+    match pool {
+        Some(pool) => pool.install(op),
+        None => op(),
+    }
+}
+
+fn mis_for_whole_graph(
+    graph: &SubDcel,
+    spanning_tree: &SpanningTree,
+    weights: &[Weight],
+    dp: DpOptions,
+    pool: Option<&ThreadPool>,
+    watch: &mut Stopwatch,
+) -> Result<Option<(Vec<VertexId>, Weight)>, SolveError> {
+    log_if_enabled!(LOG, "Solving whole graph");
+    watch.start("WholeGraph");
+    let Some(td) = graph.tree_decomposition(spanning_tree.root()) else {
+        watch.stop();
+        return Ok(Some((vec![], 0)));
+    };
+    let ntd = NiceTreeDecomposition::from(&td);
+    if let Some(limit) = dp.exceeded_limit(td.max_bag_size) {
+        log_if_enabled!(LOG, "The {limit} was hit before the whole graph was solved");
+        watch.stop();
+        return Ok(None);
+    }
+
+    let adjacency_matrix = graph.dcel.adjacency_matrix();
+    let result = in_pool(pool, || dp.solve(&adjacency_matrix, weights, &ntd));
+    watch.stop();
+
+    match result {
+        Ok((mis, weight)) => {
+            log_if_enabled!(LOG, "mis: {mis:?}, size: {}, weight: {weight}", mis.len());
+            Ok(Some((mis.into_iter().collect::<Vec<VertexId>>(), weight)))
+        }
+        Err(e) => {
+            log_if_enabled!(LOG, "Error: {e}");
+            Err(e.into())
+        }
+    }
+}
+
+/* the independent set of a single donut of the PTAS */
+struct DonutMis {
+    mis: Vec<VertexId>,
+    /// The weight that the dynamic program found, which is all that is known with `size_only`.
+    weight: Weight,
+    /// Whether the set is a maximum independent set of the donut.
+    exact: bool,
+    /// Whether the donut was solved, which it is not if a limit was hit, see
+    /// [DpOptions::exceeded_limit], or if its dynamic program failed.
+    completed: bool,
+    td_time: Duration,
+    td_width: usize,
+    time: Duration,
+    /// Why the donut could not be solved, see [MISResult::warnings].
+    warning: Option<String>,
+}
+
+/* solves a single donut, fails if its nice tree decomposition is invalid */
+fn mis_for_donut(
+    graph: &Dcel,
+    adjacency_matrix: &Vec<Vec<bool>>,
+    spanning_tree: &SpanningTree,
+    ptas_config: &PTASConfig,
+    weights: &[Weight],
+    dp: DpOptions,
+    (i, donut): (usize, &SubDcel),
+) -> Result<DonutMis, FindMisError> {
+    let start = Instant::now();
+    let mut result = DonutMis {
+        mis: vec![],
+        weight: 0,
+        exact: true,
+        completed: true,
+        td_time: Duration::ZERO,
+        td_width: 0,
+        time: Duration::ZERO,
+        warning: None,
+    };
+    if let Some(limit) = dp.exceeded_limit(0) {
+        log_if_enabled!(LOG, "Donut {i}: skipped, the {limit} was hit");
+        result.exact = false;
+        result.completed = false;
+        return Ok(result);
+    }
+    log_if_enabled!(LOG, "Donut {i}: ");
+    let td_start = Instant::now();
+    let (decomp, reduced) = if ptas_config.exact_donut_tree_decomposition {
+        /* the exact decomposition is computed for the reduced donut directly */
+        let reduced =
+            ReducedDonut::new(graph, donut.original_vertices(), &ptas_config.reduce_donuts);
+        (reduced.exact_tree_decomposition(), Some(reduced))
+    } else if !ptas_config.reduce_donuts.is_empty() {
+        /* the approximated decomposition is built from the reduced embedding of the donut */
+        let reduced =
+            ReducedDonut::new(graph, donut.original_vertices(), &ptas_config.reduce_donuts);
+        (
+            reduced.tree_decomposition(donut, spanning_tree.root()),
+            Some(reduced),
+        )
+    } else {
+        (donut.tree_decomposition(spanning_tree.root()), None)
+    };
+    result.td_time = td_start.elapsed();
+
+    let Some(decomp) = decomp else {
+        log_if_enabled!(LOG, "donut {i} was reduced completely");
+        result.mis = reduced.map_or(vec![], |reduced| reduced.transfer(vec![]));
+        result.time = start.elapsed();
+        return Ok(result);
+    };
+    result.td_width = decomp.max_bag_size.saturating_sub(1);
+    if let Some(limit) = dp.exceeded_limit(decomp.max_bag_size) {
+        log_if_enabled!(LOG, "Donut {i}: skipped, the {limit} was hit");
+        result.exact = false;
+        result.completed = false;
+        result.time = start.elapsed();
+        return Ok(result);
+    }
+
+    let ntd = NiceTreeDecomposition::from(&decomp);
+    let ntd_rels = NodeRelations::new(&ntd.td);
+    if !ntd.validate(&decomp, &ntd_rels) {
+        return Err(FindMisError::InvalidNiceTD);
+    }
+
+    #[cfg(feature = "logging")]
+    {
+        td_write_to_pdf(
+            "td",
+            format!("./logs/td_{i}").as_str(),
+            &decomp,
+            &NodeRelations::new(&decomp),
+        );
+        td_write_to_pdf(
+            "ntd",
+            format!("./logs/ntd_{i}").as_str(),
+            &ntd.td,
+            &ntd_rels,
+        );
+    }
+
+    let reduced_graph = reduced
+        .as_ref()
+        .map(|reduced| (reduced.adjacency_matrix(), reduced.weights(weights)));
+    let (adjacency_matrix, donut_weights) = match &reduced_graph {
+        Some((adjacency_matrix, weights)) => (adjacency_matrix, weights.as_slice()),
+        None => (adjacency_matrix, weights),
+    };
+    match dp.solve(adjacency_matrix, donut_weights, &ntd) {
+        Ok((mis, weight)) => {
+            log_if_enabled!(LOG, "mis: {mis:?}, size: {}, weight: {weight}", mis.len());
+            result.weight = weight;
+            result.mis = match reduced {
+                Some(reduced) => reduced.transfer(mis.into_iter().collect()),
+                None => mis.into_iter().collect(),
+            }
+        }
+        Err(e) => {
+            /* the offset misses the vertices of this donut, so it is skipped like a donut
+             * that hit a limit */
+            result.warning = Some(format!("donut {i} could not be solved: {e}"));
+            result.exact = false;
+            result.completed = false;
+        }
+    };
+    result.time = start.elapsed();
+    Ok(result)
+}
+
+/* the best offset of the PTAS */
+struct OffsetMis {
+    i: usize,
+    mis: Vec<VertexId>,
+    weight: Weight,
+    /// The smallest upper bound of all offsets, see [deleted_levels_bound].
+    bound: Weight,
+    /// The offsets whose donuts were all solved, see [MISResult::offsets].
+    offsets: Vec<usize>,
+    /// The warnings of the donuts of all offsets.
+    warnings: Vec<String>,
+}
+
+/* The donuts of all offsets are solved in parallel on the current thread pool, see
+ * [SolverConfig::with_threads]. The timings of an offset add up the times of its donuts, so they
+ * measure the work for the offset and not the time that passed. */
+fn mis_with_donut(
+    graph: &Dcel,
+    spanning_tree: &SpanningTree,
+    ptas_config: &PTASConfig,
+    weights: &[Weight],
+    dp: DpOptions,
+    pool: Option<&ThreadPool>,
+    watch: &mut Stopwatch,
+) -> Result<OffsetMis, SolveError> {
+    let adjacency_matrix = graph.adjacency_matrix();
+    let mut donuts = vec![];
+    let mut find_time = vec![];
+    for i in 0..=ptas_config.k {
+        let start = Instant::now();
+        donuts.push(graph.find_donuts_for_k(ptas_config.k, i, &spanning_tree)?);
+        find_time.push(start.elapsed());
+    }
+
+    let solve_offset = |donuts_for_i: &Vec<SubDcel>| -> Result<Vec<DonutMis>, FindMisError> {
+        donuts_for_i
+            .par_iter()
+            .enumerate()
+            .map(|donut| {
+                mis_for_donut(
+                    graph,
+                    &adjacency_matrix,
+                    spanning_tree,
+                    ptas_config,
+                    weights,
+                    dp,
+                    donut,
+                )
+            })
+            .collect()
+    };
+    /* with a time limit, the offsets are solved one after another, so the first ones complete */
+    let solve_donuts = || -> Result<Vec<Vec<DonutMis>>, FindMisError> {
+        match dp.deadline {
+            None => donuts.par_iter().map(solve_offset).collect(),
+            Some(_) => donuts.iter().map(solve_offset).collect(),
+        }
+    };
+    let results = in_pool(pool, solve_donuts).map_err(|e| match e {
+        FindMisError::InvalidNiceTD => SolveError::InvalidNiceTreeDecomposition,
+        e => e.into(),
+    })?;
+
+    let mut best_i = 0;
+    let mut best_mis = vec![];
+    let mut best_weight = 0;
+    let mut bound = Weight::MAX;
+    let mut offsets = vec![];
+    let mut warnings = vec![];
+    let levels = spanning_tree.vertex_level();
+    let mut level_weights = vec![0; spanning_tree.max_level() + 1];
+    for v in 0..graph.num_vertices() {
+        level_weights[levels[v]] += weights[v];
+    }
+    let level_bounds = level_bounds(graph, spanning_tree, weights);
+    for (i, mut results_for_i) in results.into_iter().enumerate() {
+        log_if_enabled!(LOG, "Approximation: i: {i}");
+        warnings.extend(results_for_i.iter_mut().filter_map(|r| r.warning.take()));
+        let td_time = results_for_i.iter().map(|r| r.td_time).sum();
+        let td_width = results_for_i.iter().map(|r| r.td_width).max().unwrap_or(0);
+        let time = find_time[i] + results_for_i.iter().map(|r| r.time).sum::<Duration>();
+
+        let td_kind = match ptas_config.exact_donut_tree_decomposition {
+            true => "Exact",
+            false => "Approximated",
+        };
+        watch.record(
+            format!("{td_kind} tree decompositions: i={i:?}, width={td_width}"),
+            td_time,
+        );
+
+        /* the maximum is at most the maximum of every donut plus the deleted levels */
+        let donut_weight = |r: &DonutMis| match dp.size_only {
+            true => r.weight,
+            false => r.mis.iter().map(|&v| weights[v]).sum(),
+        };
+        let donut_levels = spanning_tree.donut_levels(ptas_config.k, i);
+        let donuts_bound: Weight = results_for_i
+            .iter()
+            .zip(&donut_levels)
+            .map(|(r, range)| match r.exact {
+                true => donut_weight(r),
+                false => level_weights[range.clone()].iter().sum(),
+            })
+            .sum();
+        let deleted_bound = deleted_levels_bound(&level_bounds, &donut_levels);
+        bound = bound.min(donuts_bound + deleted_bound);
+
+        /* an offset with a skipped donut has no independent set of the whole graph */
+        if !results_for_i.iter().all(|r| r.completed) {
+            watch.record(format!("Approximation: i={i:?}, skipped"), time);
+            continue;
+        }
+        offsets.push(i);
+
+        /* the donuts are disjoint, so without the sets their weights are added */
+        let size_only_weight = results_for_i.iter().map(|r| r.weight).sum();
+        let mis_for_i: Vec<VertexId> = results_for_i.into_iter().flat_map(|r| r.mis).collect();
+        let weight_for_i: Weight = match dp.size_only {
+            true => size_only_weight,
+            false => mis_for_i.iter().map(|&v| weights[v]).sum(),
+        };
+        log_if_enabled!(
+            LOG,
+            "mis: {mis_for_i:?}, size: {}, weight: {weight_for_i}",
+            mis_for_i.len()
+        );
+        let set = HashSet::from_iter(mis_for_i.iter().copied());
+        if let [u, v, ..] = find_connected_vertices(&set, &adjacency_matrix)[..] {
+            return Err(SolveError::NotIndependent(u, v));
+        }
+
+        if weight_for_i > best_weight {
+            best_weight = weight_for_i;
+            best_mis = mis_for_i;
+            best_i = i;
+        }
+
+        watch.record(format!("Approximation: i={i:?}"), time);
+    }
+    /* the best set is a lower bound of the maximum as well */
+    Ok(OffsetMis {
+        i: best_i,
+        mis: best_mis,
+        weight: best_weight,
+        bound: bound.max(best_weight),
+        offsets,
+        warnings,
+    })
+}
+
+/* An upper bound for the weight of an independent set of the levels that lie between the
+ * `donut_levels` of an offset, see [SpanningTree::donut_levels]. Every level except the root is
+ * left out for exactly one offset. For k >= 1, no two of these levels are adjacent, so the bound
+ * is the sum of the bounds of the single levels, see [level_bounds]. */
+fn deleted_levels_bound(level_bounds: &[Weight], donut_levels: &[Range<usize>]) -> Weight {
+    (0..level_bounds.len())
+        .filter(|level| !donut_levels.iter().any(|range| range.contains(level)))
+        .map(|level| level_bounds[level])
+        .sum()
+}
+
+/* The clique cover bound of every level of the spanning tree on its own, see
+ * [clique_cover_bound]. The cliques of a single cover of the graph without the edges between
+ * levels are the cliques of the levels, so this takes linear time for all levels. */
+fn level_bounds(graph: &Dcel, spanning_tree: &SpanningTree, weights: &[Weight]) -> Vec<Weight> {
+    let levels = spanning_tree.vertex_level();
+    let mut same_level = QuickGraph::new(graph.num_vertices());
+    for (v, neighborhood) in same_level.adjacency.iter_mut().enumerate() {
+        *neighborhood = Some(
+            graph
+                .neighbors(v)
+                .into_iter()
+                .filter(|&u| levels[u] == levels[v])
+                .collect(),
+        );
+    }
+    let mut bounds = vec![0; spanning_tree.max_level() + 1];
+    for v in clique_cover(&same_level, weights) {
+        bounds[levels[v]] += weights[v];
+    }
+    bounds
+}
+
+/* the subgraph that is induced by the vertices in `contains`, the others are removed */
+fn induced_quick_graph(graph: &Dcel, contains: impl Fn(VertexId) -> bool) -> QuickGraph {
+    let mut induced = QuickGraph::new(graph.num_vertices());
+    for (v, neighborhood) in induced.adjacency.iter_mut().enumerate() {
+        *neighborhood = contains(v).then(|| {
+            graph
+                .neighbors(v)
+                .into_iter()
+                .filter(|&u| contains(u))
+                .collect()
+        });
+    }
+    induced
+}
+
+/* the set of the heuristic, which is used if a limit was hit before any offset was solved */
+fn mis_with_heuristic(graph: &Dcel, weights: &[Weight], watch: &mut Stopwatch) -> Vec<VertexId> {
+    let quick_graph = induced_quick_graph(graph, |_| true);
+    watch.start("Greedy");
+    let greedy = find_greedy_mis(&quick_graph, weights);
+    watch.stop();
+    watch.start("Local search");
+    let mis = improve_mis(&quick_graph, weights, &greedy);
+    watch.stop();
+    mis
+}
+
+/* solves the overlapping `donuts` of every offset and keeps the lightest dominating set */
+fn ds_with_donut(
+    graph: &Dcel,
+    offsets: RangeInclusive<usize>,
+    donuts: impl Fn(usize) -> Vec<OverlappingDonut>,
+    weights: &[Weight],
+    watch: &mut Stopwatch,
+) -> (usize, Vec<VertexId>) {
+    let adjacency_matrix = graph.adjacency_matrix();
+    let mut best_i = 0;
+    let mut best_ds = vec![];
+    let mut best_weight = None;
+    for i in offsets {
+        log_if_enabled!(LOG, "Approximation: i: {i}");
+        watch.start(format!("Approximation: i={i:?}").as_str());
+
+        let mut ds_for_i = HashSet::new();
+        let mut td_time = Duration::ZERO;
+        let mut td_width = 0;
+        for (j, donut) in donuts(i).iter().enumerate() {
+            let td_start = Instant::now();
+            let decomp = donut.tree_decomposition(graph);
+            td_time += td_start.elapsed();
+            let Some(decomp) = decomp else {
+                log_if_enabled!(LOG, "donut {j} is empty");
+                continue;
+            };
+            td_width = td_width.max(decomp.max_bag_size.saturating_sub(1));
+
+            let ntd = NiceTreeDecomposition::from(&decomp);
+            let (ds, weight) =
+                find_dominating_set(&adjacency_matrix, weights, &donut.dominate, &ntd);
+            log_if_enabled!(LOG, "ds: {ds:?}, size: {}, weight: {weight}", ds.len());
+            ds_for_i.extend(ds);
+        }
+        watch.record(
+            format!("Exact tree decompositions: i={i:?}, width={td_width}"),
+            td_time,
+        );
+
+        let weight_for_i: Weight = ds_for_i.iter().map(|&v| weights[v]).sum();
+        log_if_enabled!(
+            LOG,
+            "ds: {ds_for_i:?}, size: {}, weight: {weight_for_i}",
+            ds_for_i.len()
+        );
+        if best_weight.is_none_or(|best| weight_for_i < best) {
+            best_weight = Some(weight_for_i);
+            best_ds = ds_for_i.into_iter().collect();
+            best_i = i;
+        }
+
+        watch.stop();
+    }
+    (best_i, best_ds)
+}
+
+/* what a scheme found for a connected graph */
+struct ComponentSolution {
+    set: Vec<VertexId>,
+    weight: Weight,
+    /// The maximum independent set weighs at most this, the minimum dominating set at least.
+    bound: Weight,
+    k: usize,
+    i: usize,
+    root: VertexId,
+    max_level: usize,
+    /// The PTAS only approximates a component that does not fit into a single donut.
+    exact: bool,
+    /// A limit was hit, see [MISResult::partial].
+    partial: bool,
+    /// See [MISResult::offsets].
+    offsets: Vec<usize>,
+    /// See [MISResult::warnings].
+    warnings: Vec<String>,
+}
+
+impl ComponentSolution {
+    fn new(root: VertexId, max_level: usize) -> Self {
+        ComponentSolution {
+            set: vec![],
+            weight: 0,
+            bound: 0,
+            k: max_level,
+            i: 0,
+            root,
+            max_level,
+            exact: true,
+            partial: false,
+            offsets: vec![],
+            warnings: vec![],
+        }
+    }
+}
+
+/* The spanning tree only reaches the component of its root, so every connected component is
+ * solved with its own spanning tree and donuts. The sets of the components are combined, the
+ * root and `i` of the result are the ones of the component of a given root, or else of the
+ * largest component. */
+fn solve_components(
+    dcel_builder: &DcelBuilder,
+    graph: &Dcel,
+    selection: RootSelection,
+    weights: &[Weight],
+    watch: &mut Stopwatch,
+    mut solve: impl FnMut(
+        &Dcel,
+        VertexId,
+        &[Weight],
+        &mut Stopwatch,
+    ) -> Result<ComponentSolution, SolveError>,
+) -> Result<ComponentSolution, SolveError> {
+    watch.start("Connected components");
+    let components = graph.connected_components();
+    watch.stop();
+    if components.len() == 1 {
+        watch.start("Root selection");
+        let root = select_root(graph, selection);
+        watch.stop();
+        return solve(graph, root, weights, watch);
+    }
+
+    let mut combined = ComponentSolution::new(0, 0);
+    let mut largest = 0;
+    for component in components {
+        let solution = match component[..] {
+            /* an isolated vertex is part of every maximal independent set and dominating set */
+            [v] => ComponentSolution {
+                set: vec![0],
+                weight: weights[v],
+                bound: weights[v],
+                ..ComponentSolution::new(0, 0)
+            },
+            _ => {
+                let component_graph = dcel_builder.subgraph(&component).build();
+                let component_weights: Vec<Weight> =
+                    component.iter().map(|&v| weights[v]).collect();
+                /* a given root is only used for its own component */
+                let selection = match selection {
+                    RootSelection::Vertex(root) => component
+                        .iter()
+                        .position(|&v| v == root)
+                        .map_or(RootSelection::default(), RootSelection::Vertex),
+                    selection => selection,
+                };
+                watch.start("Root selection");
+                let root = select_root(&component_graph, selection);
+                watch.stop();
+                solve(&component_graph, root, &component_weights, watch)?
+            }
+        };
+
+        combined
+            .set
+            .extend(solution.set.iter().map(|&v| component[v]));
+        combined.weight += solution.weight;
+        combined.bound += solution.bound;
+        combined.k = combined.k.max(solution.k);
+        combined.max_level = combined.max_level.max(solution.max_level);
+        combined.exact &= solution.exact;
+        combined.partial |= solution.partial;
+        combined.warnings.extend(solution.warnings);
+        let reported = match selection {
+            RootSelection::Vertex(root) => component.contains(&root),
+            _ => component.len() > largest,
+        };
+        if reported {
+            largest = component.len();
+            combined.i = solution.i;
+            combined.root = component[solution.root];
+            combined.offsets = solution.offsets;
+        }
+    }
+    Ok(combined)
+}
+
+/* solves a connected graph with the PTAS, or exactly if it fits into a single donut */
+fn mis_with_spanning_tree(
+    graph: &Dcel,
+    root: VertexId,
+    scheme: &Scheme,
+    weights: &[Weight],
+    dp: DpOptions,
+    pool: Option<&ThreadPool>,
+    watch: &mut Stopwatch,
+) -> Result<ComponentSolution, SolveError> {
+    watch.start("Spanning Tree");
+    let spanning_tree = graph.spanning_tree(root);
+    watch.stop();
+    let mut solution = ComponentSolution::new(root, spanning_tree.max_level());
+
+    match scheme {
+        Scheme::PTAS { config } if config.k <= spanning_tree.max_level() => {
+            solution.warnings.extend(dp.check_memory_budget(config.k));
+            let best = mis_with_donut(graph, &spanning_tree, config, weights, dp, pool, watch)?;
+            solution.k = config.k;
+            solution.i = best.i;
+            solution.exact = false;
+            solution.partial = best.offsets.len() <= config.k;
+            (solution.set, solution.weight, solution.bound) = (best.mis, best.weight, best.bound);
+            if best.offsets.is_empty() {
+                log_if_enabled!(LOG, "No offset was solved, using the heuristic");
+                solution.set = mis_with_heuristic(graph, weights, watch);
+                solution.weight = solution.set.iter().map(|&v| weights[v]).sum();
+            }
+            solution.offsets = best.offsets;
+            solution.warnings.extend(best.warnings);
+        }
+        _ => {
+            if let Scheme::PTAS { config } = scheme {
+                log_if_enabled!(
+                    LOG,
+                    "k = {} is larger than the {} levels, solving the whole graph",
+                    config.k,
+                    spanning_tree.max_level()
+                );
+                /* every larger k solves the whole graph as well, the cap keeps k + 1 in range */
+                solution.k = config.k.min(graph.num_vertices());
+            }
+            solution
+                .warnings
+                .extend(dp.check_memory_budget(spanning_tree.max_level()));
+            let subdcel = graph.whole_graph_donut(&spanning_tree)?;
+            match mis_for_whole_graph(&subdcel, &spanning_tree, weights, dp, pool, watch)? {
+                Some((set, weight)) => {
+                    (solution.set, solution.weight, solution.bound) = (set, weight, weight);
+                }
+                None => {
+                    solution.set = mis_with_heuristic(graph, weights, watch);
+                    solution.weight = solution.set.iter().map(|&v| weights[v]).sum();
+                    solution.bound =
+                        clique_cover_bound(&induced_quick_graph(graph, |_| true), weights);
+                    solution.exact = false;
+                    solution.partial = true;
+                }
+            }
+        }
+    }
+    Ok(solution)
+}
+
+/* returns the root of the spanning tree, a given root has to be a vertex of the graph */
+fn select_root(graph: &Dcel, selection: RootSelection) -> VertexId {
+    match selection {
+        _ if graph.num_vertices() == 0 => 0,
+        RootSelection::First => 0,
+        RootSelection::Center => pseudo_diameter_center(graph, 0),
+        RootSelection::Eccentricity => min_eccentricity_vertex(graph, 0, ROOT_SAMPLES),
+        RootSelection::Vertex(root) => root,
+    }
+}
+
+/* solves the donuts of a connected graph with the PTAS, or the whole graph as a single donut */
+fn ds_with_spanning_tree(
+    graph: &Dcel,
+    root: VertexId,
+    scheme: &Scheme,
+    weights: &[Weight],
+    watch: &mut Stopwatch,
+) -> ComponentSolution {
+    watch.start("Spanning Tree");
+    let spanning_tree = graph.spanning_tree(root);
+    watch.stop();
+    let mut solution = ComponentSolution::new(root, spanning_tree.max_level());
+
+    match scheme {
+        Scheme::PTAS { config } if config.k <= spanning_tree.max_level() => {
+            let k = config.k;
+            let donuts = |i| find_overlapping_donuts(&spanning_tree, k, i);
+            let (i, ds) = ds_with_donut(graph, 0..=k, donuts, weights, watch);
+            solution.k = k;
+            solution.i = i;
+            solution.exact = false;
+            solution.set = ds;
+            solution.offsets = (0..=k).collect();
+        }
+        _ => {
+            let donuts = |_| vec![OverlappingDonut::whole_graph(graph.num_vertices())];
+            let (_, ds) = ds_with_donut(graph, 0..=0, donuts, weights, watch);
+            solution.set = ds;
+        }
+    }
+    solution.weight = solution.set.iter().map(|&v| weights[v]).sum();
+    /* the best offset is at most (k + 3) / (k + 1) times as heavy as the minimum */
+    solution.bound = match solution.exact {
+        true => solution.weight,
+        false => (solution.weight * (solution.k + 1)).div_ceil(solution.k + 3),
+    };
+    solution
+}
+
+/// Finds a (minimum) dominating set with the scheme.
+/// The PTAS solves the overlapping donuts of [find_overlapping_donuts] for every offset, the
+/// other schemes solve the whole graph exactly with a tree decomposition.
+fn find_min_dominating_set(
+    dcel_builder: &mut DcelBuilder,
+    scheme: Scheme,
+    weights: Vec<Weight>,
+    root_selection: RootSelection,
+) -> Result<MISResult, SolveError> {
+    let mut watch = Stopwatch::new();
+    let start_time = Instant::now();
+    let graph = dcel_builder.build();
+    let adjacency_matrix = graph.adjacency_matrix();
+
+    let mut k = 0;
+    let mut best_i = 0;
+    let mut root = 0;
+    let mut max_level = 0;
+    let mut exact = true;
+    let mut bound = None;
+    let mut offsets = vec![];
+    let mut warnings = vec![];
+    let mut result = match &scheme {
+        _ if graph.num_vertices() == 0 => vec![],
+
+        Scheme::Heuristic => return Err(SolveError::UnsupportedHeuristics(Problem::DominatingSet)),
+
+        /* the exact schemes solve the whole graph with an exact tree decomposition */
+        Scheme::PTAS { .. } | Scheme::AllWithTD | Scheme::Exhaustive { .. } => {
+            let solution = solve_components(
+                dcel_builder,
+                &graph,
+                root_selection,
+                &weights,
+                &mut watch,
+                |graph, root, weights, watch| {
+                    Ok(ds_with_spanning_tree(graph, root, &scheme, weights, watch))
+                },
+            )?;
+            k = solution.k;
+            best_i = solution.i;
+            root = solution.root;
+            max_level = solution.max_level;
+            exact = solution.exact;
+            bound = Some(solution.bound);
+            offsets = solution.offsets;
+            warnings = solution.warnings;
+            solution.set
+        }
+    };
+    result.sort_unstable();
+
+    if let Some(v) = find_undominated_vertex(&adjacency_matrix, &result) {
+        return Err(SolveError::NotDominated(v));
+    }
+
+    let weight = result.iter().map(|&v| weights[v]).sum();
+    /* the best offset is at most 1 + 2 / (k + 1) times as heavy as the minimum */
+    let guarantee = match exact {
+        true => 1.0,
+        false => (k + 3) as f64 / (k + 1) as f64,
+    };
+    Ok(MISResult {
+        problem: Problem::DominatingSet,
+        scheme: scheme.name().to_string(),
+        timings: watch.timings,
+        total_time: start_time.elapsed(),
+        weight,
+        guarantee,
+        optimum_bound: bound.unwrap_or(weight),
+        result,
+        k,
+        i: best_i,
+        root,
+        max_level,
+        /* the overlapping donuts are not the donuts of the independent set PTAS */
+        has_donuts: false,
+        partial: false,
+        offsets,
+        graph: GraphStats::from(&graph),
+        reductions: vec![],
+        cover: None,
+        warnings,
+    })
+}
+
+fn reduce_input_graph(
+    dcel_builder: &mut DcelBuilder,
+    quick_graph: &mut QuickGraph,
+    reductions: &Vec<Reduction>,
+    vertex_ids: &mut HashMap<VertexId, VertexId>,
+) -> Reductions {
+    let mut found_reductions: Reductions = Reductions::default();
+
+    for input_reduction in reductions.iter() {
+        match input_reduction {
+            Reduction::NodalFold => {
+                found_reductions.nodal_folds = do_nodal_fold_reductions(quick_graph);
+                found_reductions.nodal_folds.iter().for_each(|nodal_fold| {
+                    nodal_fold.reduce_dcel_builder(dcel_builder, vertex_ids)
+                });
+            }
+            Reduction::IsolatedClique => {
+                found_reductions.isolated_cliques = do_isolated_clique_reductions(quick_graph);
+                found_reductions
+                    .isolated_cliques
+                    .iter()
+                    .for_each(|isolated_clique| {
+                        isolated_clique.reduce_dcel_builder(dcel_builder, vertex_ids)
+                    });
+            }
+            Reduction::Twin => {
+                found_reductions.twins = do_twin_reductions(quick_graph);
+                found_reductions.twins.iter().for_each(|twin_reduction| {
+                    twin_reduction.reduce_dcel_builder(dcel_builder, vertex_ids)
+                });
+            }
+        };
+    }
+    found_reductions
+}
+
+/// Maps the vertices of the reduced graph back to the vertices of the original graph.
+/// `vertex_ids` maps every remaining original vertex to its index in the reduced graph.
+fn reduced_to_original(vertex_ids: &HashMap<VertexId, VertexId>) -> Vec<VertexId> {
+    let mut original = vec![0; vertex_ids.len()];
+    vertex_ids
+        .iter()
+        .for_each(|(&original_index, &reduced_index)| original[reduced_index] = original_index);
+    original
+}
+
+fn transfer_reductions(
+    reduce_input: &[Reduction],
+    reductions: &mut Reductions,
+    independence_set: &mut Vec<VertexId>,
+    reduced_to_original: &[VertexId],
+) {
+    /* reconstruct original vertex indices */
+    for vertex in independence_set.iter_mut() {
+        *vertex = reduced_to_original[*vertex];
+    }
+
+    reductions.transfer(reduce_input, independence_set);
+}
+
+/* returns an edge of the graph without a vertex in the cover */
+fn find_uncovered_edge(graph: &QuickGraph, cover: &[VertexId]) -> Option<(VertexId, VertexId)> {
+    let cover: HashSet<VertexId> = cover.iter().copied().collect();
+    graph
+        .adjacency
+        .iter()
+        .enumerate()
+        .filter(|(u, _)| !cover.contains(u))
+        .find_map(|(u, neighborhood)| {
+            neighborhood
+                .iter()
+                .flatten()
+                .find(|v| !cover.contains(v))
+                .map(|&v| (u, v))
+        })
+}
+
+/* the vertices of the original graph that are not in the lifted independent set */
+fn vertex_cover(
+    graph: &QuickGraph,
+    independence_set: &[VertexId],
+    weights: &[Weight],
+    max_set_weight: Weight,
+) -> Result<VertexCover, SolveError> {
+    let set: HashSet<VertexId> = independence_set.iter().copied().collect();
+    let vertices: Vec<VertexId> = (0..graph.adjacency.len())
+        .filter(|v| !set.contains(v))
+        .collect();
+    if let Some((u, v)) = find_uncovered_edge(graph, &vertices) {
+        return Err(SolveError::NotCovered(u, v));
+    }
+
+    let total: Weight = weights.iter().sum();
+    Ok(VertexCover {
+        weight: vertices.iter().map(|&v| weights[v]).sum(),
+        lower_bound: total.saturating_sub(max_set_weight),
+        vertices,
+    })
+}
+
+/* returns two vertices of the set that are adjacent in the graph, if there are any */
+fn find_adjacent_pair(
+    graph: &QuickGraph,
+    independence_set: &[VertexId],
+) -> Option<(VertexId, VertexId)> {
+    let set: HashSet<VertexId> = independence_set.iter().copied().collect();
+    independence_set.iter().find_map(|&u| {
+        graph.adjacency[u]
+            .iter()
+            .flatten()
+            .find(|v| set.contains(v))
+            .map(|&v| (u, v))
+    })
+}
+
+fn find_max_independent_set(
+    dcel_builder: &mut DcelBuilder,
+    quick_graph: &mut QuickGraph,
+    config: SolverConfig,
+    weights: Vec<Weight>,
+) -> Result<MISResult, SolveError> {
+    let SolverConfig {
+        scheme,
+        problem,
+        threads,
+        local_search,
+        root,
+        ..
+    } = config;
+    let dp = DpOptions {
+        size_only: config.size_only,
+        parallel: config.parallel_dp,
+        memory_budget: config.memory_budget,
+        deadline: config.time_limit.map(|limit| Instant::now() + limit),
+        memory_limit: config.memory_limit,
+    };
+    let pool = match threads {
+        Some(threads) => Some(ThreadPoolBuilder::new().num_threads(threads).build()?),
+        None => None,
+    };
+    let mut watch = Stopwatch::new();
+    let start_time = Instant::now();
+    let scheme_name = scheme.name().to_string();
+    let donut_scheme = matches!(scheme, Scheme::PTAS { .. } | Scheme::AllWithTD);
+    let reduce_input = scheme.input_reductions().to_vec();
+
+    /* the reductions change the quick graph, so the original adjacencies are kept for the check */
+    let original_graph = QuickGraph::from(&*dcel_builder);
+    let original_stats =
+        (!reduce_input.is_empty()).then(|| GraphStats::from(&dcel_builder.clone().build()));
+
+    /* initialize table with vertex indices */
+    let mut vertex_ids: HashMap<VertexId, VertexId> = HashMap::new();
+    (0..quick_graph.adjacency.len()).for_each(|vertex| {
+        vertex_ids.insert(vertex, vertex);
+    });
+
+    /* the reductions are applied to the builder, so the reduced graph is solved */
+    watch.start("Applying approximations");
+    let mut input_reductions: Reductions =
+        reduce_input_graph(dcel_builder, quick_graph, &reduce_input, &mut vertex_ids);
+    let applied_reductions = input_reductions.applied(&reduce_input);
+    let reduced_to_original = reduced_to_original(&vertex_ids);
+    let reduced_weights: Vec<Weight> = reduced_to_original.iter().map(|&v| weights[v]).collect();
+    watch.stop();
+
+    let graph: Dcel = dcel_builder.build();
+    let graph_stats = original_stats.unwrap_or_else(|| GraphStats::from(&graph));
+
+    /* a given root may have been removed by the reductions */
+    let root_selection = match root {
+        RootSelection::Vertex(v) => vertex_ids
+            .get(&v)
+            .map_or(RootSelection::default(), |&v| RootSelection::Vertex(v)),
+        selection => selection,
+    };
+
+    let mut k = 0;
+    let mut best_i = 0;
+    let mut root = 0;
+    let mut max_level = 0;
+    /* the PTAS only approximates if the graph does not fit into a single donut */
+    let mut exact = true;
+    let mut bound = Weight::MAX;
+    /* a limit was hit, so not all offsets were solved */
+    let mut partial = false;
+    let mut offsets = vec![];
+    let mut warnings = vec![];
+
+    let (mut result, set_weight) = match &scheme {
+        _ if graph.num_vertices() == 0 => {
+            log_if_enabled!(LOG, "The reductions removed all vertices");
+            (vec![], 0)
+        }
+
+        Scheme::PTAS { .. } | Scheme::AllWithTD => {
+            let solution = solve_components(
+                dcel_builder,
+                &graph,
+                root_selection,
+                &reduced_weights,
+                &mut watch,
+                |graph, root, weights, watch| {
+                    mis_with_spanning_tree(graph, root, &scheme, weights, dp, pool.as_ref(), watch)
+                },
+            )?;
+            k = solution.k;
+            best_i = solution.i;
+            root = solution.root;
+            max_level = solution.max_level;
+            exact = solution.exact;
+            bound = solution.bound;
+            partial = solution.partial;
+            offsets = solution.offsets;
+            warnings = solution.warnings;
+            /* isolated vertices are added even without the sets of the dynamic programs */
+            match dp.size_only {
+                true => (vec![], solution.weight),
+                false => (solution.set, solution.weight),
+            }
+        }
+
+        Scheme::Exhaustive { .. } => {
+            watch.start("Root selection");
+            root = select_root(&graph, root_selection);
+            watch.stop();
+            max_level = graph.spanning_tree(root).max_level();
+            k = max_level;
+            watch.start("Branch and reduce");
+            let (mis, weight) =
+                find_mis_branch_and_reduce(&QuickGraph::from(&*dcel_builder), &reduced_weights);
+            watch.stop();
+            match dp.size_only {
+                true => (vec![], weight),
+                false => (mis, weight),
+            }
+        }
+
+        Scheme::Heuristic => {
+            let reduced_graph = QuickGraph::from(&*dcel_builder);
+            watch.start("Greedy");
+            let greedy = find_greedy_mis(&reduced_graph, &reduced_weights);
+            watch.stop();
+            watch.start("Local search");
+            let mis = improve_mis(&reduced_graph, &reduced_weights, &greedy);
+            watch.stop();
+            exact = false;
+            let weight = mis.iter().map(|&v| reduced_weights[v]).sum();
+            match dp.size_only {
+                true => (vec![], weight),
+                false => (mis, weight),
+            }
+        }
+    };
+    /* the clique cover bound of the reduced graph may be below the bound of the scheme */
+    let bound = match exact {
+        true => set_weight,
+        false => bound.min(clique_cover_bound(
+            &QuickGraph::from(&*dcel_builder),
+            &reduced_weights,
+        )),
+    };
+
+    transfer_reductions(
+        &reduce_input,
+        &mut input_reductions,
+        &mut result,
+        &reduced_to_original,
+    );
+    if let Some((u, v)) = find_adjacent_pair(&original_graph, &result) {
+        return Err(SolveError::NotIndependent(u, v));
+    }
+    /* the reductions add the same weight to the set and to the maximum */
+    let optimum_bound = match dp.size_only {
+        true => bound,
+        false => bound + result.iter().map(|&v| weights[v]).sum::<Weight>() - set_weight,
+    };
+    if local_search {
+        /* the search only adds weight, so the bound of the scheme still holds for the result */
+        watch.start("Improving the set");
+        result = improve_mis(&original_graph, &weights, &result);
+        watch.stop();
+    }
+
+    let end_time = Instant::now();
+    let total_time = end_time.duration_since(start_time);
+    /* the reductions add vertices to the set, so its weight is only known after the transfer */
+    let weight: Weight = match dp.size_only {
+        true => set_weight,
+        false => result.iter().map(|&v| weights[v]).sum(),
+    };
+    /* the best of the k + 1 offsets of the PTAS misses at most 1 / (k + 1) of the maximum, the
+     * heuristic has k = 0 and no guarantee, and neither has a partial result */
+    let guarantee = match (exact, partial) {
+        (true, _) => 1.0,
+        (false, true) => 0.0,
+        (false, false) => k as f64 / (k + 1) as f64,
+    };
+    let cover = match problem {
+        Problem::IndependentSet | Problem::DominatingSet => None,
+        Problem::VertexCover => {
+            /* an independent set of weight w shows that the maximum weighs at most w (k + 1) / k */
+            let max_set_weight = match (exact, partial) {
+                (true, _) => weight,
+                (false, true) => optimum_bound,
+                (false, false) => (weight * (k + 1))
+                    .checked_div(k)
+                    .unwrap_or(Weight::MAX)
+                    .min(optimum_bound),
+            };
+            let cover = vertex_cover(&original_graph, &result, &weights, max_set_weight)?;
+            Some(cover)
+        }
+    };
+
+    Ok(MISResult {
+        problem,
+        scheme: scheme_name,
+        timings: watch.timings,
+        total_time,
+        result,
+        weight,
+        guarantee,
+        optimum_bound,
+        k,
+        i: best_i,
+        root: reduced_to_original.get(root).copied().unwrap_or(root),
+        max_level,
+        has_donuts: donut_scheme && k > 0,
+        partial,
+        offsets,
+        graph: graph_stats,
+        reductions: applied_reductions,
+        cover,
+        warnings,
+    })
+}
+
+/// Finds a (maximum) independent set of the planar graph described by the embedding in
+/// `graph` with the scheme configured in `config`.
+/// The builder is not modified, reductions are applied to a copy of it.
+pub fn solve(graph: &DcelBuilder, config: SolverConfig) -> Result<MISResult, SolveError> {
+    solve_graph(graph.clone(), QuickGraph::from(graph), config)
+}
+
+/// Same as [`solve`], but takes both representations of the graph, e.g. from [`load_graph`],
+/// so that none of them has to be derived again.
+/// Fails with [`SolveError::InvalidEmbedding`] if [`validate_embedding`] finds a fatal issue,
+/// the other issues are returned in [`MISResult::warnings`].
+pub fn solve_graph(
+    dcel_builder: DcelBuilder,
+    quick_graph: QuickGraph,
+    config: SolverConfig,
+) -> Result<MISResult, SolveError> {
+    let warnings = embedding_warnings(&dcel_builder)?;
+    solve_valid_graph(dcel_builder, quick_graph, config, warnings)
+}
+
+/* the issues of the embedding that are returned as warnings, fails for a fatal issue */
+fn embedding_warnings(dcel_builder: &DcelBuilder) -> Result<Vec<String>, SolveError> {
+    match validate_embedding(dcel_builder) {
+        Err(invalid) if invalid.is_fatal() => Err(invalid.into()),
+        Err(invalid) => Ok(vec![invalid.to_string()]),
+        Ok(()) => Ok(vec![]),
+    }
+}
+
+/* solves the graph whose embedding was checked by [embedding_warnings] */
+fn solve_valid_graph(
+    mut dcel_builder: DcelBuilder,
+    mut quick_graph: QuickGraph,
+    mut config: SolverConfig,
+    mut warnings: Vec<String>,
+) -> Result<MISResult, SolveError> {
+    let vertex_count = dcel_builder.num_vertices();
+    let weights = config
+        .weights
+        .take()
+        .unwrap_or_else(|| vec![1; vertex_count]);
+    if weights.len() != vertex_count {
+        return Err(SolveError::WeightCount {
+            expected: vertex_count,
+            found: weights.len(),
+        });
+    }
+    let reductions = match &config.scheme {
+        Scheme::PTAS { config } => {
+            !config.reduce_input.is_empty() || !config.reduce_donuts.is_empty()
+        }
+        scheme => !scheme.input_reductions().is_empty(),
+    };
+    if reductions && config.problem == Problem::DominatingSet {
+        return Err(SolveError::UnsupportedReductions(config.problem));
+    }
+    if reductions && weights.iter().any(|&weight| weight != weights[0]) {
+        return Err(SolveError::WeightedReductions);
+    }
+    if config.size_only
+        && (reductions || config.local_search || config.problem != Problem::IndependentSet)
+    {
+        return Err(SolveError::SizeOnly);
+    }
+    if let RootSelection::Vertex(root) = config.root {
+        if root >= vertex_count {
+            return Err(SolveError::InvalidRoot(root));
+        }
+    }
+    if matches!(&config.scheme, Scheme::PTAS { config } if config.k == 0) {
+        return Err(SolveError::InvalidK);
+    }
+    if config.local_search && config.problem == Problem::DominatingSet {
+        return Err(SolveError::UnsupportedHeuristics(config.problem));
+    }
+    let mut result = match config.problem {
+        Problem::DominatingSet => {
+            find_min_dominating_set(&mut dcel_builder, config.scheme, weights, config.root)
+        }
+        Problem::IndependentSet | Problem::VertexCover => {
+            find_max_independent_set(&mut dcel_builder, &mut quick_graph, config, weights)
+        }
+    }?;
+    warnings.append(&mut result.warnings);
+    result.warnings = warnings;
+    Ok(result)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        load_graph, solve, write_web_file, GraphFormat, PTASConfig, Problem, Reduction,
+        RootSelection, Scheme, SolveError, SolverConfig,
+    };
+    use crate::graph::mis_finder::find_connected_vertices;
+    use crate::read_graph_file_into_dcel_builder;
+    use std::collections::HashSet;
+
+    /* the PTAS with approximated tree decompositions and without reductions, the tests change
+     * the fields they need */
+    fn ptas_config(k: usize) -> PTASConfig {
+        PTASConfig {
+            k,
+            exact_donut_tree_decomposition: false,
+            reduce_input: vec![],
+            reduce_donuts: vec![],
+        }
+    }
+
+    fn ptas(k: usize) -> Scheme {
+        Scheme::PTAS {
+            config: ptas_config(k),
+        }
+    }
+
+    #[test]
+    fn reduced_input() {
+        let reductions = [
+            vec![Reduction::NodalFold],
+            vec![Reduction::IsolatedClique],
+            vec![Reduction::Twin],
+            vec![
+                Reduction::Twin,
+                Reduction::NodalFold,
+                Reduction::IsolatedClique,
+            ],
+        ];
+        for file in ["tree", "tri", "simple", "merge_test"] {
+            let builder = read_graph_file_into_dcel_builder(&format!("data/{file}.graph")).unwrap();
+            let adjacency = builder.clone().build().adjacency_matrix();
+            let web_file = std::env::temp_dir().join("thm_ptas_reduced_input.js");
+            let web_file = web_file.to_str().unwrap();
+            let exhaustive = |reduce_input: Vec<Reduction>| Scheme::Exhaustive { reduce_input };
+            let optimum = solve(&builder, SolverConfig::new(exhaustive(vec![])))
+                .unwrap()
+                .result
+                .len();
+
+            for reduce_input in reductions.iter().cloned() {
+                let exact_ptas = Scheme::PTAS {
+                    config: PTASConfig {
+                        exact_donut_tree_decomposition: true,
+                        reduce_input: reduce_input.clone(),
+                        ..ptas_config(2)
+                    },
+                };
+                for scheme in [exhaustive(reduce_input.clone()), exact_ptas] {
+                    let exact = matches!(scheme, Scheme::Exhaustive { .. });
+                    let mis_result = solve(&builder, SolverConfig::new(scheme)).unwrap();
+                    let result = &mis_result.result;
+                    let set: HashSet<usize> = result.iter().copied().collect();
+                    assert_eq!(set.len(), result.len());
+                    assert!(find_connected_vertices(&set, &adjacency).is_empty());
+                    if exact {
+                        assert_eq!(result.len(), optimum, "{file} {reduce_input:?}");
+                    }
+                    /* a fully reduced graph has no donuts to write */
+                    write_web_file(web_file, &builder.clone().build(), mis_result).unwrap();
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn weighted() {
+        let exact_ptas = |reduce_input: Vec<Reduction>| Scheme::PTAS {
+            config: PTASConfig {
+                exact_donut_tree_decomposition: true,
+                reduce_input,
+                ..ptas_config(2)
+            },
+        };
+        for file in ["tree", "tri", "simple"] {
+            let builder = read_graph_file_into_dcel_builder(&format!("data/{file}.graph")).unwrap();
+            let weights: Vec<usize> = (0..builder.num_vertices()).map(|v| v * 7 % 5 + 1).collect();
+            let weighted = |scheme| SolverConfig::new(scheme).with_weights(weights.clone());
+            let optimum = solve(
+                &builder,
+                weighted(Scheme::Exhaustive {
+                    reduce_input: vec![],
+                }),
+            )
+            .unwrap();
+            assert_eq!(
+                optimum.weight,
+                optimum.result.iter().map(|&v| weights[v]).sum::<usize>()
+            );
+
+            for scheme in [Scheme::AllWithTD, exact_ptas(vec![])] {
+                let result = solve(&builder, weighted(scheme)).unwrap();
+                assert_eq!(result.weight, optimum.weight, "{file}");
+            }
+            assert!(matches!(
+                solve(&builder, weighted(exact_ptas(vec![Reduction::Twin]))),
+                Err(SolveError::WeightedReductions)
+            ));
+        }
+
+        let builder = read_graph_file_into_dcel_builder("data/tri.graph").unwrap();
+        let config = SolverConfig::new(Scheme::AllWithTD).with_weights(vec![1; 3]);
+        assert!(matches!(
+            solve(&builder, config),
+            Err(SolveError::WeightCount {
+                expected: 4,
+                found: 3
+            })
+        ));
+    }
+
+    #[test]
+    fn size_only() {
+        let ptas = |k, exact_donut_tree_decomposition, reduce_donuts| Scheme::PTAS {
+            config: PTASConfig {
+                exact_donut_tree_decomposition,
+                reduce_donuts,
+                ..ptas_config(k)
+            },
+        };
+        for file in ["simple", "bsp3"] {
+            let builder = read_graph_file_into_dcel_builder(&format!("data/{file}.graph")).unwrap();
+            let weights: Vec<usize> = (0..builder.num_vertices()).map(|v| v % 3 + 1).collect();
+            for (k, exact) in [(1, false), (2, false), (2, true), (100, false)] {
+                let config =
+                    || SolverConfig::new(ptas(k, exact, vec![])).with_weights(weights.clone());
+                let expected = solve(&builder, config()).unwrap();
+                let result = solve(&builder, config().with_size_only()).unwrap();
+                assert!(result.result.is_empty());
+                assert_eq!(result.weight, expected.weight, "{file}, k = {k}");
+            }
+        }
+
+        let builder = read_graph_file_into_dcel_builder("data/tri.graph").unwrap();
+        for config in [
+            SolverConfig::new(ptas(1, false, vec![Reduction::Twin])),
+            SolverConfig::new(Scheme::AllWithTD).with_problem(Problem::VertexCover),
+        ] {
+            assert!(matches!(
+                solve(&builder, config.with_size_only()),
+                Err(SolveError::SizeOnly)
+            ));
+        }
+    }
+
+    #[test]
+    fn threads() {
+        let ptas = |exact_donut_tree_decomposition| Scheme::PTAS {
+            config: PTASConfig {
+                exact_donut_tree_decomposition,
+                reduce_donuts: vec![Reduction::Twin],
+                ..ptas_config(2)
+            },
+        };
+        let builder = read_graph_file_into_dcel_builder("data/bsp3.graph").unwrap();
+        for exact in [false, true] {
+            let sequential = solve(&builder, SolverConfig::new(ptas(exact)).with_threads(1));
+            let sequential = sequential.unwrap();
+            let parallel = solve(&builder, SolverConfig::new(ptas(exact)).with_threads(4));
+            let parallel = parallel.unwrap();
+            assert_eq!(parallel.weight, sequential.weight);
+            assert_eq!(parallel.i, sequential.i);
+            let offsets = |timings: &[(String, _)]| {
+                timings
+                    .iter()
+                    .filter(|(name, _)| name.starts_with("Approximation: i="))
+                    .count()
+            };
+            assert_eq!(offsets(&parallel.timings), 3);
+            assert_eq!(offsets(&sequential.timings), 3);
+        }
+    }
+
+    #[test]
+    fn parallel_dp() {
+        let builder = read_graph_file_into_dcel_builder("data/bsp3.graph").unwrap();
+        for k in [1, 2, 100] {
+            let sequential = solve(&builder, SolverConfig::new(ptas(k))).unwrap();
+            let config = SolverConfig::new(ptas(k))
+                .with_parallel_dp()
+                .with_threads(3);
+            let parallel = solve(&builder, config).unwrap();
+            let sorted = |mut set: Vec<usize>| {
+                set.sort_unstable();
+                set
+            };
+            assert_eq!(sorted(parallel.result), sorted(sequential.result));
+            assert_eq!(parallel.weight, sequential.weight);
+        }
+    }
+
+    #[test]
+    fn heuristic() {
+        let builder = read_graph_file_into_dcel_builder("data/bsp3.graph").unwrap();
+        let optimum = solve(&builder, SolverConfig::new(Scheme::AllWithTD))
+            .unwrap()
+            .weight;
+        let heuristic = solve(&builder, SolverConfig::new(Scheme::Heuristic)).unwrap();
+        assert!(heuristic.weight <= optimum);
+        assert_eq!(heuristic.weight, heuristic.result.len());
+
+        let approximation = solve(&builder, SolverConfig::new(ptas(1))).unwrap();
+        let improved = solve(&builder, SolverConfig::new(ptas(1)).with_local_search()).unwrap();
+        assert!(improved.weight >= approximation.weight);
+        assert!(improved.weight <= optimum);
+
+        let dominating_set = |config: SolverConfig| config.with_problem(Problem::DominatingSet);
+        for config in [
+            SolverConfig::new(Scheme::Heuristic),
+            SolverConfig::new(ptas(1)).with_local_search(),
+        ] {
+            assert!(matches!(
+                solve(&builder, dominating_set(config)),
+                Err(SolveError::UnsupportedHeuristics(Problem::DominatingSet))
+            ));
+        }
+    }
+
+    #[test]
+    fn root_selection() {
+        let builder = read_graph_file_into_dcel_builder("data/bsp2.graph").unwrap();
+        let config = |root: RootSelection| SolverConfig::new(Scheme::AllWithTD).with_root(root);
+        let solve_with = |root: RootSelection| solve(&builder, config(root));
+        let first = solve_with(RootSelection::First).unwrap();
+        assert_eq!(first.root, 0);
+
+        let center = solve_with(RootSelection::Center).unwrap();
+        let eccentricity = solve_with(RootSelection::Eccentricity).unwrap();
+
+        let vertex = solve_with(RootSelection::Vertex(5)).unwrap();
+        assert_eq!(vertex.root, 5);
+        for result in [center, eccentricity, vertex] {
+            assert_eq!(result.weight, first.weight);
+        }
+
+        assert!(matches!(
+            solve_with(RootSelection::Vertex(1000)),
+            Err(SolveError::InvalidRoot(1000))
+        ));
+
+        /* the PTAS contracts the levels above each donut into the root, so it works with every
+         * root, and a central root leaves fewer levels */
+        let adjacency_matrix = builder.clone().build().adjacency_matrix();
+        let solve_ptas = |problem: Problem, root: RootSelection| {
+            let config = SolverConfig::new(ptas(1)).with_problem(problem);
+            solve(&builder, config.with_root(root)).unwrap()
+        };
+        let first = solve_ptas(Problem::IndependentSet, RootSelection::First);
+        for root in [
+            RootSelection::Center,
+            RootSelection::Eccentricity,
+            RootSelection::Vertex(5),
+        ] {
+            let result = solve_ptas(Problem::IndependentSet, root);
+            if root != RootSelection::Vertex(5) {
+                assert!(result.max_level <= first.max_level);
+            }
+            assert!(result.weight > 0);
+            let set: HashSet<usize> = result.result.iter().copied().collect();
+            assert_eq!(set.len(), result.result.len());
+            assert!(find_connected_vertices(&set, &adjacency_matrix).is_empty());
+
+            assert!(solve_ptas(Problem::VertexCover, root).cover.is_some());
+            solve_ptas(Problem::DominatingSet, root);
+        }
+    }
+
+    #[test]
+    fn empty_graph() {
+        let input = std::env::temp_dir().join("thm_ptas_empty.graph");
+        std::fs::write(&input, "0\n0\n").unwrap();
+        let builder = read_graph_file_into_dcel_builder(input.to_str().unwrap()).unwrap();
+        let result = solve(&builder, SolverConfig::new(Scheme::AllWithTD)).unwrap();
+        assert!(result.result.is_empty());
+
+        let web_file = std::env::temp_dir().join("thm_ptas_empty.js");
+        let web_file = web_file.to_str().unwrap();
+        assert!(write_web_file(web_file, &builder.clone().build(), result).is_err());
+    }
+
+    #[test]
+    fn loaded_graph() {
+        let loaded = load_graph("data/bsp1.graph", GraphFormat::Embedding).unwrap();
+        let vertex_count = loaded.dcel_builder.num_vertices();
+        let (original, result) = loaded.solve(SolverConfig::new(Scheme::AllWithTD)).unwrap();
+        assert_eq!(original.num_vertices(), vertex_count);
+        assert!(result.weight > 0);
+    }
+
+    #[test]
+    fn zero_k() {
+        let builder = read_graph_file_into_dcel_builder("data/bsp1.graph").unwrap();
+        for problem in [Problem::IndependentSet, Problem::DominatingSet] {
+            let config = SolverConfig::new(ptas(0)).with_problem(problem);
+            assert!(matches!(solve(&builder, config), Err(SolveError::InvalidK)));
+        }
+    }
+
+    #[test]
+    fn epsilon() {
+        use crate::graph::dyn_table::dt_dense::table_bytes;
+
+        assert_eq!(PTASConfig::k_for_epsilon(0.05), Some(19));
+        assert_eq!(PTASConfig::k_for_epsilon(0.25), Some(3));
+        assert_eq!(PTASConfig::k_for_epsilon(0.3), Some(3));
+        assert_eq!(PTASConfig::k_for_epsilon(0.5), Some(1));
+        assert_eq!(PTASConfig::k_for_epsilon(0.9), Some(1));
+        assert_eq!(PTASConfig::k_for_epsilon(0.0), None);
+        assert_eq!(PTASConfig::k_for_epsilon(1.0), None);
+        /* epsilons that meet a guarantee exactly, 0.05 and 0.2 are stored slightly above it */
+        assert_eq!(PTASConfig::k_for_epsilon(0.2), Some(4));
+        assert_eq!(PTASConfig::k_for_epsilon(1.0 / 4.0), Some(3));
+        assert_eq!(PTASConfig::k_for_epsilon(f64::next_up(1.0 / 3.0)), Some(2));
+        /* 1 / 3 is stored slightly below a third, so k = 2 would miss the guarantee */
+        assert_eq!(PTASConfig::k_for_epsilon(1.0 / 3.0), Some(3));
+        assert_eq!(PTASConfig::k_for_epsilon(f64::next_down(0.25)), Some(4));
+
+        /* bsp1 has fewer than 19 levels, so the whole graph is solved */
+        let builder = read_graph_file_into_dcel_builder("data/bsp1.graph").unwrap();
+        let optimum = solve(&builder, SolverConfig::new(Scheme::AllWithTD)).unwrap();
+        let scheme = || ptas(PTASConfig::k_for_epsilon(0.05).unwrap());
+        /* the 5 levels may give bags of 16 vertices, which only warns about a smaller budget */
+        let config = SolverConfig::new(scheme()).with_memory_budget(table_bytes(15));
+        let result = solve(&builder, config).unwrap();
+        assert!(result.warnings[0].contains("memory budget"));
+        assert_eq!(result.weight, optimum.weight);
+        let dp = super::DpOptions {
+            size_only: false,
+            parallel: false,
+            memory_budget: Some(table_bytes(15)),
+            deadline: None,
+            memory_limit: None,
+        };
+        assert!(dp.check_memory_budget(5).unwrap().contains("memory budget"));
+        assert_eq!(dp.check_memory_budget(4), None);
+        let result = solve(&builder, SolverConfig::new(scheme())).unwrap();
+        assert!(result.max_level < 19);
+        assert_eq!((result.k, result.guarantee), (19, 1.0));
+        assert_eq!(result.weight, optimum.weight);
+
+        /* a tiny epsilon saturates k, which is capped for the result and its web file */
+        assert_eq!(PTASConfig::k_for_epsilon(1e-20), Some(usize::MAX));
+        let result = solve(&builder, SolverConfig::new(ptas(usize::MAX))).unwrap();
+        assert_eq!((result.k, result.guarantee), (builder.num_vertices(), 1.0));
+        assert_eq!(result.weight, optimum.weight);
+        let web_file = std::env::temp_dir().join("thm_ptas_epsilon.js");
+        let web_file = web_file.to_str().unwrap();
+        write_web_file(web_file, &builder.clone().build(), result).unwrap();
+    }
+
+    #[test]
+    fn limits() {
+        use crate::graph::dyn_table::dt_dense::table_bytes;
+        use std::time::Duration;
+
+        let builder = read_graph_file_into_dcel_builder("data/bsp3.graph").unwrap();
+        let optimum = solve(&builder, SolverConfig::new(Scheme::AllWithTD)).unwrap();
+        let complete = solve(&builder, SolverConfig::new(ptas(1))).unwrap();
+        assert!(!complete.partial);
+        assert_eq!(complete.offsets, vec![0, 1]);
+
+        /* the donuts of offset 0 have bags of 4 vertices, the ones of offset 1 of 3 */
+        let config = SolverConfig::new(ptas(1)).with_memory_limit(table_bytes(3));
+        let result = solve(&builder, config).unwrap();
+        assert!(result.partial);
+        assert_eq!((result.offsets.clone(), result.i), (vec![1], 1));
+        assert_eq!(result.guarantee, 0.0);
+        assert!(result.weight > 0);
+        assert!(result.optimum_bound >= optimum.weight);
+
+        /* the budget only warns, the offsets are still solved */
+        let config = SolverConfig::new(ptas(1)).with_memory_budget(table_bytes(3));
+        let result = solve(&builder, config).unwrap();
+        assert!(!result.partial);
+        assert_eq!(result.offsets, vec![0, 1]);
+        assert!(result.warnings[0].contains("memory budget"));
+        /* the bags that are expected for k = 1 fit into it, so there is no warning */
+        let config = SolverConfig::new(ptas(1)).with_memory_budget(table_bytes(4));
+        assert!(solve(&builder, config).unwrap().warnings.is_empty());
+
+        /* without any solved offset, the heuristic is used */
+        for scheme in [ptas(1), Scheme::AllWithTD] {
+            let config = SolverConfig::new(scheme).with_time_limit(Duration::ZERO);
+            let result = solve(&builder, config).unwrap();
+            assert!(result.partial);
+            assert!(result.offsets.is_empty());
+            assert_eq!(result.guarantee, 0.0);
+            assert!(result.weight > 0 && result.weight <= optimum.weight);
+            assert!(result.optimum_bound >= optimum.weight);
+        }
+    }
+
+    #[test]
+    fn certificate() {
+        let reduced_ptas = |k| Scheme::PTAS {
+            config: PTASConfig {
+                reduce_input: vec![Reduction::Twin],
+                ..ptas_config(k)
+            },
+        };
+        for file in ["bsp1", "bsp3"] {
+            let builder = read_graph_file_into_dcel_builder(&format!("data/{file}.graph")).unwrap();
+            let optimum = solve(&builder, SolverConfig::new(Scheme::AllWithTD)).unwrap();
+            assert_eq!(optimum.guarantee, 1.0);
+            assert_eq!(optimum.optimum_bound, optimum.weight);
+
+            for k in 1..=2 {
+                for config in [
+                    SolverConfig::new(ptas(k)),
+                    SolverConfig::new(ptas(k)).with_size_only(),
+                    SolverConfig::new(reduced_ptas(k)),
+                ] {
+                    let result = solve(&builder, config).unwrap();
+                    assert_eq!(result.guarantee, k as f64 / (k + 1) as f64);
+                    assert!(result.optimum_bound >= optimum.weight, "{file}, k={k}");
+                    assert!(result.ratio() <= 1.0);
+                    assert!(result.weight as f64 >= result.guarantee * optimum.weight as f64);
+                }
+            }
+
+            let heuristic = solve(&builder, SolverConfig::new(Scheme::Heuristic)).unwrap();
+            assert_eq!(heuristic.guarantee, 0.0);
+            assert!(heuristic.optimum_bound >= optimum.weight);
+        }
+
+        let builder = read_graph_file_into_dcel_builder("data/bsp3.graph").unwrap();
+        let dominating_set =
+            |scheme| SolverConfig::new(scheme).with_problem(Problem::DominatingSet);
+        let minimum = solve(&builder, dominating_set(Scheme::AllWithTD)).unwrap();
+        let result = solve(&builder, dominating_set(ptas(1))).unwrap();
+        assert_eq!(result.guarantee, 2.0);
+        assert!(result.optimum_bound <= minimum.weight);
+        assert!(result.ratio() >= 1.0);
+    }
+
+    #[test]
+    fn bound_of_paths() {
+        use crate::graph::mis_finder::find_weighted_mis_exhaustive;
+        use crate::graph::DcelBuilder;
+
+        /* short paths have donuts without arcs, whose vertices still count for the bound */
+        let mut cases = vec![vec![1, 1, 1], vec![4, 2, 5]];
+        for n in 2..=8 {
+            cases.push((0..n).map(|v| v * 7 % 5 + 1).collect());
+        }
+        for weights in cases {
+            let mut builder = DcelBuilder::new();
+            for v in 1..weights.len() {
+                builder.push_arc(v - 1, v);
+                builder.push_arc(v, v - 1);
+            }
+            let adjacency_matrix = builder.clone().build().adjacency_matrix();
+            let (_, optimum) = find_weighted_mis_exhaustive(&adjacency_matrix, &weights).unwrap();
+            for k in 1..=3 {
+                let config = SolverConfig::new(ptas(k)).with_weights(weights.clone());
+                let result = solve(&builder, config).unwrap();
+                assert!(result.weight <= optimum, "{weights:?}, k={k}");
+                assert!(result.optimum_bound >= optimum, "{weights:?}, k={k}");
+            }
+        }
+    }
+
+    #[test]
+    fn vertex_cover() {
+        let exact_ptas = || Scheme::PTAS {
+            config: PTASConfig {
+                exact_donut_tree_decomposition: true,
+                reduce_donuts: vec![Reduction::Twin],
+                ..ptas_config(1)
+            },
+        };
+        let instances = [
+            ("tree", Scheme::AllWithTD),
+            ("tri", Scheme::AllWithTD),
+            ("simple", Scheme::AllWithTD),
+            ("simple", exact_ptas()),
+            ("bsp3", exact_ptas()),
+        ];
+        for (file, scheme) in instances {
+            let builder = read_graph_file_into_dcel_builder(&format!("data/{file}.graph")).unwrap();
+            let graph = builder.clone().build();
+            let n = graph.num_vertices();
+            let exact = matches!(scheme, Scheme::AllWithTD);
+            let config = SolverConfig::new(scheme).with_problem(Problem::VertexCover);
+            let result = solve(&builder, config).unwrap();
+            let cover = result.cover.unwrap();
+            assert_eq!(cover.vertices.len() + result.result.len(), n);
+            assert_eq!(cover.weight, cover.vertices.len());
+            assert!(cover.lower_bound <= cover.weight);
+            if exact {
+                assert_eq!(cover.lower_bound, cover.weight, "{file}");
+            }
+
+            let cover: HashSet<usize> = cover.vertices.into_iter().collect();
+            for u in 0..n {
+                for v in graph.neighbors(u) {
+                    assert!(cover.contains(&u) || cover.contains(&v));
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn dominating_set() {
+        use crate::graph::dominating_set::{
+            find_dominating_set_exhaustive, find_undominated_vertex,
+        };
+
+        let dominating_set =
+            |scheme| SolverConfig::new(scheme).with_problem(Problem::DominatingSet);
+        for file in ["tree", "tri", "simple"] {
+            let builder = read_graph_file_into_dcel_builder(&format!("data/{file}.graph")).unwrap();
+            let adjacency = builder.clone().build().adjacency_matrix();
+            let weights = vec![1; adjacency.len()];
+            let (_, optimum) = find_dominating_set_exhaustive(&adjacency, &weights);
+            let result = solve(&builder, dominating_set(Scheme::AllWithTD)).unwrap();
+            assert_eq!(result.weight, optimum, "{file}");
+        }
+
+        let builder = read_graph_file_into_dcel_builder("data/bsp3.graph").unwrap();
+        let adjacency = builder.clone().build().adjacency_matrix();
+        for k in 1..=2 {
+            let result = solve(&builder, dominating_set(ptas(k))).unwrap();
+            assert_eq!(find_undominated_vertex(&adjacency, &result.result), None);
+        }
+
+        /* the exhaustive scheme uses a tree decomposition, so it is not limited by 2^n subsets */
+        let exhaustive = Scheme::Exhaustive {
+            reduce_input: vec![],
+        };
+        let result = solve(&builder, dominating_set(exhaustive)).unwrap();
+        let minimum = solve(&builder, dominating_set(Scheme::AllWithTD)).unwrap();
+        assert_eq!(result.guarantee, 1.0);
+        assert_eq!(result.weight, minimum.weight);
+        assert_eq!(find_undominated_vertex(&adjacency, &result.result), None);
+
+        let reduced = SolverConfig::new(Scheme::Exhaustive {
+            reduce_input: vec![Reduction::Twin],
+        })
+        .with_problem(Problem::DominatingSet);
+        assert!(matches!(
+            solve(&builder, reduced),
+            Err(SolveError::UnsupportedReductions(Problem::DominatingSet))
+        ));
+    }
+
+    #[test]
+    fn disconnected() {
+        use crate::graph::DcelBuilder;
+
+        let builder = read_graph_file_into_dcel_builder("data/bsp3.graph").unwrap();
+        let n = builder.num_vertices();
+        /* two copies of the graph with the isolated vertex n between them */
+        let mut disconnected = DcelBuilder::new();
+        for offset in [0, n + 1] {
+            for v in 0..n {
+                for arc in builder.arcs(v) {
+                    disconnected.push_arc(v + offset, builder.arc(arc).dst + offset);
+                }
+            }
+        }
+        let graph = disconnected.clone().build();
+        assert_eq!(graph.connected_components().len(), 3);
+        assert!(!graph.spanning_tree(0).is_spanning());
+
+        for problem in [Problem::IndependentSet, Problem::DominatingSet] {
+            for scheme in [|| ptas(2), || Scheme::AllWithTD] {
+                let config = |scheme: Scheme| SolverConfig::new(scheme).with_problem(problem);
+                let single = solve(&builder, config(scheme())).unwrap();
+                let result = solve(&disconnected, config(scheme())).unwrap();
+                assert_eq!(result.weight, 2 * single.weight + 1);
+                assert_eq!(result.result.len(), 2 * single.result.len() + 1);
+                assert!(result.result.contains(&n));
+                assert_eq!(result.max_level, single.max_level);
+            }
+        }
+
+        /* the given root is used for its own component only */
+        let config = SolverConfig::new(Scheme::AllWithTD).with_root(RootSelection::Vertex(n + 6));
+        assert_eq!(solve(&disconnected, config).unwrap().root, n + 6);
+    }
+}
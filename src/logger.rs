@@ -44,12 +44,15 @@ impl Logger {
 
 #[macro_export]
 macro_rules! log_if_enabled {
-    ($filepath:expr, $($arg:tt)*) => {
+    ($filepath:expr, $($arg:tt)*) => {{
         #[cfg(feature = "logging")]
         {
             crate::logger::LOGGER.lock().unwrap().log_to_file($filepath, &format!($($arg)*));
         }
-    };
+        /* the arguments are checked but not formatted, so they count as used */
+        #[cfg(not(feature = "logging"))]
+        let _ = ($filepath, format_args!($($arg)*));
+    }};
 }
 
 pub fn log_file(path: &str, preparation: &dyn Fn(), command: Command) {
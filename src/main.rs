@@ -1,489 +1,107 @@
-use std::collections::{HashMap, HashSet};
-use std::error::Error;
-use std::fs::File;
-use std::io::{self, BufRead};
-use std::path::{Path, PathBuf};
-use std::process::Command;
-use std::time::{Duration, Instant};
-pub mod graph;
-
-#[macro_use]
-pub mod logger;
-
-use arboretum_td::tree_decomposition::TreeDecomposition;
-use clap::Parser;
-use graph::approximated_td::{ApproximatedTD, SubTDBuilder, TDBuilder};
-
-use graph::dcel::spanning_tree::SpanningTree;
-use graph::dcel::vertex::VertexId;
-use graph::dcel_file_writer::JsDataWriter;
-
-use graph::mis_finder::{find_mis, find_mis_exhaustive};
-use graph::nice_tree_decomp::NiceTreeDecomposition;
+use std::path::PathBuf;
+use std::time::Duration;
 
-use graph::quick_graph::QuickGraph;
-use graph::sub_dcel::SubDcel;
-use graph::{Dcel, DcelBuilder};
+use clap::Parser;
 
-use crate::graph::mis_finder::find_connected_vertices;
-use crate::graph::node_relations::NodeRelations;
-use crate::graph::reductions::isolated_clique_reduction::{
-    do_isolated_clique_reductions, transfer_isolated_clique, IsolatedClique,
-};
-use crate::graph::reductions::nodal_fold_reduction::{
-    do_nodal_fold_reductions, transfer_nodal_fold_reductions, NodalFold,
+use thm_ptas::{
+    load_graph, write_graph_file, write_result_json, write_web_file, GraphFormat, PTASConfig,
+    Problem, Reduction, RootSelection, Scheme, SolverConfig, DEFAULT_MEMORY_BUDGET,
 };
-use crate::graph::reductions::twin_reduction::{
-    do_twin_reductions, transfer_twin_reductions, TwinReduction,
-};
-use crate::graph::reductions::{ApplicableReduction, Reductions};
-use crate::graph::tree_decomposition::{td_write_to_dot, td_write_to_pdf};
-
-fn read_graph_file_into_quick_graph(filename: &str) -> Result<QuickGraph, String> {
-    return if let Ok(mut lines) = read_lines(filename) {
-        /* create datastructure for graph */
-        let mut graph: QuickGraph;
-        let vertex_count: usize = lines.next().unwrap().unwrap().parse().unwrap();
-        graph = QuickGraph::new(vertex_count);
-        graph.edge_count = lines.next().unwrap().unwrap().parse().unwrap();
-
-        /* read in edges */
-        for _ in 0..(2 * graph.edge_count) {
-            let edge = lines.next().unwrap().unwrap();
-            let mut edge = edge.split(" ");
-            let u: usize = edge.next().unwrap().parse().unwrap();
-            let v: usize = edge.next().unwrap().parse().unwrap();
-            if let Some(ref mut adjacency_u) = &mut graph.adjacency[u] {
-                adjacency_u.push(v);
-            } else {
-                return Err(String::from("Could not push neighbor to adjacency list. "));
-            }
-        }
-
-        Ok(graph)
-    } else {
-        Err(format!("Could not open file {}", filename))
-    };
-}
-
-fn read_graph_file_into_dcel_builder(filename: &str) -> Result<DcelBuilder, String> {
-    return if let Ok(mut lines) = read_lines(filename) {
-        /* build datastructure for DECL */
-        let mut dcel_builder: DcelBuilder;
-        if let Some(Ok(_)) = lines.next() {
-            dcel_builder = DcelBuilder::new();
-            let edge_count: usize = lines.next().unwrap().unwrap().parse().unwrap();
-
-            /* read edges into DECL */
-            for _ in 0..(2 * edge_count) {
-                let edge = lines.next().unwrap().unwrap();
-                let mut edge = edge.split(" ");
-                let u: usize = edge.next().unwrap().parse().unwrap();
-                let v: usize = edge.next().unwrap().parse().unwrap();
-                dcel_builder.push_arc(u, v);
-            }
-        } else {
-            return Err(String::from("Error: Could not read line. "));
-        }
-
-        /* return DCEL-Builder */
-        Ok(dcel_builder)
-    } else {
-        Err(format!("Could not open file {}", filename))
-    };
-}
-
-fn read_lines<P>(filename: P) -> io::Result<io::Lines<io::BufReader<File>>>
-where
-    P: AsRef<Path>,
-{
-    let file = File::open(filename)?;
-    Ok(io::BufReader::new(file).lines())
-}
-
-fn write_web_file(filename: &str, dcel: &Dcel, result: MISResult) {
-    let mut writer = JsDataWriter::new(filename, dcel, result);
-    writer.write_data()
-}
 
 #[derive(Debug, Clone, clap::ValueEnum)]
-enum Reduction {
-    Twin,
-    IsolatedClique,
-    NodalFold,
-}
-
-struct PTASConfig {
-    k: usize,
-    exact_donut_tree_decomposition: bool,
-    reduce_input: Vec<Reduction>,
-    reduce_donuts: Vec<Reduction>,
-}
-
-enum Scheme {
-    PTAS { config: PTASConfig },
+enum CliScheme {
+    PTAS,
     AllWithTD,
-    Exhaustive { reduce_input: Vec<Reduction> },
-}
-
-#[derive(Debug)]
-pub struct MISResult {
-    timings: Vec<(String, Duration)>,
-    total_time: Duration,
-    result: Vec<VertexId>,
-    k: usize,
-    i: usize,
-}
-
-struct Stopwatch {
-    current: String,
-    current_start: Instant,
-    timings: Vec<(String, Duration)>,
-}
-
-impl Stopwatch {
-    fn new() -> Self {
-        Self {
-            timings: vec![],
-            current: String::from(""),
-            current_start: Instant::now(),
-        }
-    }
-
-    fn start(&mut self, period: &str) {
-        self.current = String::from(period);
-        self.current_start = Instant::now();
-    }
-
-    fn stop(&mut self) {
-        let stopping_time = Instant::now();
-        let duration = stopping_time.duration_since(self.current_start);
-        self.timings.push((self.current.clone(), duration));
-    }
+    Exhaustive,
+    Heuristic,
 }
 
-fn mis_for_whole_graph(
-    graph: &SubDcel,
-    spanning_tree: &SpanningTree,
-    watch: &mut Stopwatch,
-) -> Result<Vec<VertexId>, Box<dyn Error>> {
-    println!("Solving whole graph");
-    watch.start("WholeGraph");
-    let mut builder = SubTDBuilder::new(&graph, &spanning_tree, 0);
-    let td = ApproximatedTD::from(&mut builder);
-    let td = TreeDecomposition::from(&td);
-    let ntd = NiceTreeDecomposition::from(&td);
-
-    // find_mis(&graph.adjacency_matrix(), &ntd).map(|(set, size)| set.into_iter().collect())
-    let result = find_mis(&graph.dcel.adjacency_matrix(), &ntd);
-    watch.stop();
-
-    match result {
-        Ok((mis, size)) => {
-            println!("mis: {mis:?}, size: {size}");
-            Ok(mis.into_iter().collect::<Vec<VertexId>>())
-        }
-        Err(e) => {
-            println!("Error: {e}");
-            Err(Box::new(e))
-        }
-    }
+#[derive(Debug, Clone, clap::ValueEnum)]
+enum CliRootSelection {
+    First,
+    Center,
+    Eccentricity,
 }
 
-fn mis_with_donut(
-    graph: &Dcel,
-    spanning_tree: &SpanningTree,
-    ptas_config: &PTASConfig,
-    watch: &mut Stopwatch,
-) -> Result<(usize, Vec<VertexId>), Box<dyn Error>> {
-    let mut best_i = 0;
-    let mut best_mis = vec![];
-    for i in 0..=ptas_config.k {
-        println!("Approximation: i: {i}");
-        watch.start(format!("Approximation: i={i:?}").as_str());
-        // TODO use spanning tree to find donuts
-
-        let donuts = graph.find_donuts_for_k(ptas_config.k, i, &spanning_tree)?;
-        for donut_reductions in ptas_config.reduce_donuts.clone() {
-            // TODO: apply donut reduction on DCEL builders
-        }
-
-        let mut mis_for_i = vec![];
-        for (i, donut) in donuts.iter().enumerate() {
-            // continue;
-            println!("Donut {i}: ");
-            // donut
-            //     .vertex_mapping
-            //     .iter()
-            //     .for_each(|&v| println!("global v{v}"));
-            let mut td_b = SubTDBuilder::new(&donut, &spanning_tree, donut.min_lvl.unwrap());
-            let td = ApproximatedTD::from(&mut td_b);
-            if td.bags().len() == 0 {
-                println!("bags of donut are {i} empty");
-                continue;
-                //todo add all nodes of donut to MIS
-            }
+#[derive(Debug, Parser)]
+struct CliArguments {
+    #[arg(value_enum)]
+    scheme: CliScheme,
 
-            let decomp = TreeDecomposition::from(&td);
-            let ntd = NiceTreeDecomposition::from(&decomp);
-            let ntd_rels = NodeRelations::new(&ntd.td);
-            assert!(ntd.validate(&decomp, &ntd_rels));
-
-            #[cfg(feature = "logging")]
-            {
-                td_write_to_pdf(
-                    "td",
-                    format!("./logs/td_{i}").as_str(),
-                    &decomp,
-                    &NodeRelations::new(&decomp),
-                );
-                td_write_to_pdf(
-                    "ntd",
-                    format!("./logs/ntd_{i}").as_str(),
-                    &ntd.td,
-                    &ntd_rels,
-                );
-            }
+    #[arg(long, default_value_t = 1)]
+    k: usize,
 
-            // TODO: generate MIS for this donut and add to list
-            match find_mis(&graph.adjacency_matrix(), &ntd) {
-                Ok((mis, size)) => {
-                    println!("mis: {mis:?}, size: {size}");
-                    mis.into_iter().for_each(|v| mis_for_i.push(v));
-                }
-                Err(e) => {
-                    println!("Error: {e}")
-                }
-            };
-            // panic!("fuuuu u");
-        }
+    /// Derives the smallest k whose set is within this fraction of the maximum, i.e.
+    /// k / (k + 1) >= 1 - epsilon, instead of using --k.
+    #[arg(long, conflicts_with = "k")]
+    epsilon: Option<f64>,
 
-        println!("mis: {mis_for_i:?}, size: {}", mis_for_i.len());
-        assert!(find_connected_vertices(
-            &HashSet::from_iter(mis_for_i.iter().copied()),
-            &graph.adjacency_matrix(),
-        )
-        .is_empty());
-
-        if mis_for_i.len() > best_mis.len() {
-            best_mis.clear();
-            mis_for_i.iter().for_each(|i| best_mis.push(*i));
-            best_i = i;
-        }
+    /// Warns if the tables of the dynamic program may take more than this many MiB.
+    #[arg(long, default_value_t = DEFAULT_MEMORY_BUDGET >> 20)]
+    memory_budget: usize,
 
-        watch.stop();
-    }
-    Ok((best_i, best_mis))
-}
+    /// Starts no dynamic program after this many seconds and returns the best offset that was
+    /// solved so far.
+    #[arg(long)]
+    time_limit: Option<f64>,
 
-fn reduce_input_graph(
-    mut dcel_builder: &mut DcelBuilder,
-    mut quick_graph: &mut QuickGraph,
-    reductions: &Vec<Reduction>,
-    mut vertex_ids: &mut HashMap<VertexId, VertexId>,
-) -> Reductions {
-    let mut found_reductions: Reductions = Reductions::default();
-
-    for input_reduction in reductions.iter() {
-        match input_reduction {
-            Reduction::NodalFold => {
-                found_reductions.nodal_folds = do_nodal_fold_reductions(&mut quick_graph);
-                found_reductions.nodal_folds.iter().for_each(|nodal_fold| {
-                    nodal_fold.reduce_dcel_builder(&mut dcel_builder, &mut vertex_ids)
-                });
-            }
-            Reduction::IsolatedClique => {
-                found_reductions.isolated_cliques = do_isolated_clique_reductions(&mut quick_graph);
-                found_reductions
-                    .isolated_cliques
-                    .iter()
-                    .for_each(|isolated_clique| {
-                        isolated_clique.reduce_dcel_builder(&mut dcel_builder, &mut vertex_ids)
-                    });
-            }
-            Reduction::Twin => {
-                found_reductions.twins = do_twin_reductions(&mut quick_graph);
-                found_reductions.twins.iter().for_each(|twin_reduction| {
-                    twin_reduction.reduce_dcel_builder(&mut dcel_builder, &mut vertex_ids)
-                });
-            }
-        };
-    }
-    found_reductions
-}
+    /// Skips the dynamic programs whose largest table would take more than this many MiB.
+    #[arg(long)]
+    memory_limit: Option<usize>,
 
-fn transfer_reductions(
-    reduce_input: Vec<Reduction>,
-    mut reductions: &mut Reductions,
-    mut independence_set: &mut Vec<VertexId>,
-    vertex_ids: &HashMap<VertexId, VertexId>,
-) {
-    /* reconstruct original vertex indices */
-    let mut inverted_ids: HashMap<VertexId, VertexId> = HashMap::new();
-    vertex_ids
-        .iter()
-        .for_each(|(&original_index, &recent_index)| {
-            inverted_ids.insert(recent_index, original_index);
-        });
-
-    for i in 0..independence_set.len() {
-        independence_set[i] = inverted_ids[&independence_set[i]];
-    }
+    #[arg(short = 'E')]
+    exact_donut_tree_decomposition: bool,
 
-    for i in 0..reduce_input.len() {
-        let input_reduction = &reduce_input[reduce_input.len() - i - 1];
-        match input_reduction {
-            Reduction::NodalFold => {
-                transfer_nodal_fold_reductions(&mut independence_set, &mut reductions.nodal_folds);
-            }
-            Reduction::IsolatedClique => {
-                transfer_isolated_clique(&mut independence_set, &mut reductions.isolated_cliques);
-            }
-            Reduction::Twin => {
-                transfer_twin_reductions(&mut independence_set, &mut reductions.twins)
-            }
-        }
-    }
-}
+    #[arg(short = 'R')]
+    input_reductions: Vec<Reduction>,
 
-fn find_max_independent_set(
-    mut dcel_builder: &mut DcelBuilder,
-    mut quick_graph: &mut QuickGraph,
-    scheme: Scheme,
-) -> Result<MISResult, Box<dyn Error>> {
-    let mut watch = Stopwatch::new();
-    let start_time = Instant::now();
-
-    /* initialize table with vertex indices */
-    let mut vertex_ids: HashMap<VertexId, VertexId> = HashMap::new();
-    (0..quick_graph.adjacency.len()).for_each(|vertex| {
-        vertex_ids.insert(vertex, vertex);
-    });
+    #[arg(short = 'D')]
+    donut_reductions: Vec<Reduction>,
 
-    let graph: Dcel = dcel_builder.build();
-
-    let mut k = 0;
-    let mut best_i = 0;
-
-    let result = match scheme {
-        Scheme::PTAS {
-            config: ptas_config,
-        } => {
-            k = ptas_config.k;
-            watch.start("Applying approximations");
-
-            let mut input_reductions: Reductions = reduce_input_graph(
-                &mut dcel_builder,
-                &mut quick_graph,
-                &ptas_config.reduce_input,
-                &mut vertex_ids,
-            );
-
-            watch.stop();
-
-            watch.start("Find Rings");
-            // let _rings = graph.find_rings();
-            watch.stop();
-
-            let root = 0;
-            // build spanning tree
-            watch.start("Spanning Tree");
-            let spanning_tree = graph.spanning_tree(root);
-            watch.stop();
-
-            let mut result = if ptas_config.k > spanning_tree.max_level() {
-                let subdcel =
-                    &graph.find_donuts_for_k(usize::MAX - 1, usize::MAX - 1, &spanning_tree)?[0];
-                mis_for_whole_graph(&subdcel, &spanning_tree, &mut watch).unwrap()
-            } else {
-                let (i, best_mis) =
-                    mis_with_donut(&graph, &spanning_tree, &ptas_config, &mut watch).unwrap();
-                best_i = i;
-                best_mis
-            };
-
-            transfer_reductions(
-                ptas_config.reduce_input,
-                &mut input_reductions,
-                &mut result,
-                &vertex_ids,
-            );
-
-            result
-        }
+    /// The problem that is solved.
+    #[arg(long, value_enum, default_value_t = Problem::IndependentSet)]
+    problem: Problem,
 
-        Scheme::Exhaustive {
-            reduce_input: input_reductions,
-        } => {
-            let mut found_reductions: Reductions = reduce_input_graph(
-                &mut dcel_builder,
-                &mut quick_graph,
-                &input_reductions,
-                &mut vertex_ids,
-            );
-            let root = 0;
-            let spanning_tree = graph.spanning_tree(root);
-            k = spanning_tree.max_level();
-            let mut result: Vec<VertexId> = find_mis_exhaustive(&graph.adjacency_matrix())
-                .map(|(mis, _)| mis.into_iter().collect::<Vec<_>>())?;
-            transfer_reductions(
-                input_reductions,
-                &mut found_reductions,
-                &mut result,
-                &vertex_ids,
-            );
-            result
-        }
+    /// Only computes the weight of the maximum independent set without its vertices, which
+    /// needs less memory.
+    #[arg(long)]
+    size_only: bool,
 
-        Scheme::AllWithTD => {
-            let root = 0;
-            watch.start("Spanning Tree");
-            let spanning_tree = graph.spanning_tree(root);
-            k = spanning_tree.max_level();
-            let subdcel =
-                &graph.find_donuts_for_k(usize::MAX - 1, usize::MAX - 1, &spanning_tree)?[0];
-            println!("{:?}", subdcel.vertex_mapping);
-            println!("{}", subdcel.vertex_mapping.len());
-            watch.stop();
-            mis_for_whole_graph(&subdcel, &spanning_tree, &mut watch)?
-        }
-    };
+    /// Number of threads that solve the donuts of the PTAS, all CPUs are used by default.
+    #[arg(long)]
+    threads: Option<usize>,
 
-    let end_time = Instant::now();
-    let total_time = end_time.duration_since(start_time);
+    /// Also computes the tables of the dynamic program in parallel, which helps for wide donuts.
+    #[arg(long)]
+    parallel_dp: bool,
 
-    Ok(MISResult {
-        timings: watch.timings,
-        total_time,
-        result,
-        k,
-        i: best_i,
-    })
-}
+    /// How the root of the spanning tree is chosen, which determines the levels of the donuts.
+    #[arg(long, value_enum, default_value_t = CliRootSelection::First)]
+    root_selection: CliRootSelection,
 
-#[derive(Debug, Clone, clap::ValueEnum)]
-enum CliScheme {
-    PTAS,
-    AllWithTD,
-    Exhaustive,
-}
+    /// Uses this vertex as the root of the spanning tree instead of --root-selection.
+    #[arg(long)]
+    root: Option<usize>,
 
-#[derive(Debug, Parser)]
-struct CliArguments {
-    #[arg(value_enum)]
-    scheme: CliScheme,
+    /// Improves the independent set of the scheme with a local search.
+    #[arg(long)]
+    local_search: bool,
 
-    #[arg(long, default_value_t = 1)]
-    k: usize,
+    /// Format of the input file, guessed by the file extension if it is not given.
+    #[arg(long, value_enum)]
+    format: Option<GraphFormat>,
 
-    #[arg(short = 'E')]
-    exact_donut_tree_decomposition: bool,
+    /// Writes the embedded input graph to this file.
+    #[arg(long)]
+    export: Option<PathBuf>,
 
-    #[arg(short = 'R')]
-    input_reductions: Vec<Reduction>,
+    /// Format of the exported graph, guessed by the file extension if it is not given.
+    #[arg(long, value_enum)]
+    export_format: Option<GraphFormat>,
 
-    #[arg(short = 'D')]
-    donut_reductions: Vec<Reduction>,
+    /// Writes the result, the timings and the graph statistics as JSON to this file.
+    #[arg(long)]
+    result_json: Option<PathBuf>,
 
     #[arg(value_hint = clap::ValueHint::DirPath)]
     input: PathBuf,
@@ -501,7 +119,18 @@ fn main() {
     }
 
     let args = CliArguments::parse();
-    println!("{args:?}");
+
+    let k = match args.epsilon.map(PTASConfig::k_for_epsilon) {
+        None => args.k,
+        Some(Some(k)) => {
+            println!("k = {k} for epsilon = {}", args.epsilon.unwrap());
+            k
+        }
+        Some(None) => {
+            eprintln!("The epsilon has to be between 0 and 1");
+            std::process::exit(1);
+        }
+    };
 
     let scheme = match args.scheme {
         CliScheme::Exhaustive => Scheme::Exhaustive {
@@ -509,52 +138,121 @@ fn main() {
         },
         CliScheme::PTAS => Scheme::PTAS {
             config: PTASConfig {
-                k: args.k,
+                k,
                 exact_donut_tree_decomposition: args.exact_donut_tree_decomposition,
                 reduce_input: args.input_reductions,
                 reduce_donuts: args.donut_reductions,
             },
         },
         CliScheme::AllWithTD => Scheme::AllWithTD {},
+        CliScheme::Heuristic => Scheme::Heuristic,
     };
 
-    let mut dcel_b = match read_graph_file_into_dcel_builder(args.input.to_str().unwrap()) {
+    let format = args
+        .format
+        .unwrap_or_else(|| GraphFormat::from_path(&args.input));
+    let mut loaded_graph = match load_graph(&args.input, format) {
         Ok(result) => result,
-        Err(error) => panic!("Failed to read graph file into DCEL: {:?}", error),
+        Err(error) => {
+            eprintln!("Failed to read {}: {error}", args.input.display());
+            std::process::exit(1);
+        }
     };
 
-    let mut dcel_b2 = dcel_b.clone();
-
-    let mut quick_graph: QuickGraph =
-        match read_graph_file_into_quick_graph(args.input.to_str().unwrap()) {
-            Ok(result) => result,
-            Err(error) => panic!("Failed to read graph file into quick graph: {:?}", error),
-        };
+    if let Some(export) = &args.export {
+        let export_format = args
+            .export_format
+            .unwrap_or_else(|| GraphFormat::from_path(export));
+        if let Err(error) = write_graph_file(export, export_format, &loaded_graph.arc_list) {
+            eprintln!("Failed to write {}: {error}", export.display());
+            std::process::exit(1);
+        }
+    }
 
-    // write_web_file(&args.output, &dcel);
-    let mis_result = match find_max_independent_set(&mut dcel_b, &mut quick_graph, scheme) {
+    let mut config = SolverConfig::new(scheme)
+        .with_problem(args.problem)
+        .with_memory_budget(args.memory_budget.saturating_mul(1 << 20));
+    if let Some(weights) = loaded_graph.arc_list.weights.take() {
+        config = config.with_weights(weights);
+    }
+    if args.size_only {
+        config = config.with_size_only();
+    }
+    if let Some(threads) = args.threads {
+        config = config.with_threads(threads);
+    }
+    if args.parallel_dp {
+        config = config.with_parallel_dp();
+    }
+    if args.local_search {
+        config = config.with_local_search();
+    }
+    if let Some(seconds) = args.time_limit {
+        match Duration::try_from_secs_f64(seconds) {
+            Ok(time_limit) => config = config.with_time_limit(time_limit),
+            Err(_) => {
+                eprintln!("The time limit has to be a non-negative number of seconds");
+                std::process::exit(1);
+            }
+        }
+    }
+    if let Some(mib) = args.memory_limit {
+        config = config.with_memory_limit(mib.saturating_mul(1 << 20));
+    }
+    config = config.with_root(match (args.root, args.root_selection) {
+        (Some(root), _) => RootSelection::Vertex(root),
+        (None, CliRootSelection::First) => RootSelection::First,
+        (None, CliRootSelection::Center) => RootSelection::Center,
+        (None, CliRootSelection::Eccentricity) => RootSelection::Eccentricity,
+    });
+    let (original, mis_result) = match loaded_graph.solve(config) {
         Ok(result) => result,
-        Err(error) => panic!("Failed computing maximum independent set: {error:?}"),
+        Err(error) => {
+            eprintln!("Failed solving {}: {error}", args.problem.name());
+            std::process::exit(1);
+        }
     };
 
+    for warning in &mis_result.warnings {
+        eprintln!("Warning: {warning}");
+    }
     println!("Result: {mis_result:?}");
-    println!("Size of MIS: {:?}", mis_result.result.len());
-
-    //    let args: Vec<String> = env::args().collect();
-
-    //    for a in BfsIter::new(&dcel, 0) {
-    //        print!("{:?}", a);
-    //    }
-
-    //    //dcel.triangulate();
-
-    let dcel2 = &dcel_b2.build();
-    // println!("FACES: {:?}", dcel.faces().len());
-    // println!("FACES 2: {:?}", dcel.faces().len());
+    let set_name = match args.problem {
+        Problem::DominatingSet => "dominating set",
+        Problem::IndependentSet | Problem::VertexCover => "MIS",
+    };
+    if !args.size_only {
+        println!("Size of {set_name}: {:?}", mis_result.result.len());
+    }
+    println!("Weight of {set_name}: {:?}", mis_result.weight);
+    let bound_name = match args.problem {
+        Problem::DominatingSet => "Lower bound of the minimum dominating set",
+        Problem::IndependentSet | Problem::VertexCover => "Upper bound of the MIS",
+    };
+    println!("{bound_name}: {:?}", mis_result.optimum_bound);
+    println!("Guarantee: {:.4}", mis_result.guarantee);
+    println!("Ratio to the bound: {:.4}", mis_result.ratio());
+    if mis_result.partial {
+        println!(
+            "Partial result, a limit was hit. Solved offsets: {:?}",
+            mis_result.offsets
+        );
+    }
+    if let Some(cover) = &mis_result.cover {
+        println!("Size of vertex cover: {:?}", cover.vertices.len());
+        println!("Weight of vertex cover: {:?}", cover.weight);
+        println!("Lower bound of vertex cover: {:?}", cover.lower_bound);
+    }
 
-    write_web_file(&args.output, &dcel2, mis_result);
-    //    // let mut dg = DualGraph::new(&st);
-    //    // dg.build();
+    if let Some(result_json) = &args.result_json {
+        if let Err(error) = write_result_json(result_json, &mis_result) {
+            eprintln!("Failed to write {}: {error}", result_json.display());
+            std::process::exit(1);
+        }
+    }
 
-    //    println!("{:?}", dcel);
+    if let Err(error) = write_web_file(&args.output, &original, mis_result) {
+        eprintln!("Failed to write {}: {error}", args.output);
+        std::process::exit(1);
+    }
 }
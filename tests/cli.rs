@@ -0,0 +1,66 @@
+use std::process::Command;
+
+#[test]
+fn missing_twin() {
+    /* the arc 0 -> 1 has no twin 1 -> 0 */
+    let input = std::env::temp_dir().join("thm_ptas_missing_twin.graph");
+    std::fs::write(&input, "3\n2\n0 1\n1 2\n2 0\n0 2\n").unwrap();
+    let output = std::env::temp_dir().join("thm_ptas_missing_twin.js");
+
+    let run = Command::new(env!("CARGO_BIN_EXE_thm-ptas"))
+        .arg("ptas")
+        .arg(&input)
+        .arg(&output)
+        .output()
+        .unwrap();
+    let stderr = String::from_utf8_lossy(&run.stderr);
+    assert_eq!(run.status.code(), Some(1), "{stderr}");
+    assert!(stderr.contains("has no twin"), "{stderr}");
+    assert!(!stderr.contains("panicked"), "{stderr}");
+}
+
+#[cfg(unix)]
+#[test]
+fn non_utf8_paths() {
+    use std::ffi::OsStr;
+    use std::os::unix::ffi::OsStrExt;
+
+    let dir = std::env::temp_dir();
+    let input = dir.join(OsStr::from_bytes(b"thm_ptas_\xff.graph"));
+    std::fs::copy("data/bsp1.graph", &input).unwrap();
+    let result_json = dir.join(OsStr::from_bytes(b"thm_ptas_\xff.json"));
+    let export = dir.join(OsStr::from_bytes(b"thm_ptas_\xff_export.graph"));
+    let output = dir.join("thm_ptas_non_utf8.js");
+
+    let run = Command::new(env!("CARGO_BIN_EXE_thm-ptas"))
+        .arg("ptas")
+        .arg("--result-json")
+        .arg(&result_json)
+        .arg("--export")
+        .arg(&export)
+        .arg(&input)
+        .arg(&output)
+        .output()
+        .unwrap();
+    let stderr = String::from_utf8_lossy(&run.stderr);
+    assert!(run.status.success(), "{stderr}");
+    assert!(result_json.exists());
+    assert!(export.exists());
+}
+
+#[test]
+fn invalid_time_limit() {
+    for time_limit in ["-1", "NaN", "1e300"] {
+        let run = Command::new(env!("CARGO_BIN_EXE_thm-ptas"))
+            .arg("ptas")
+            .arg(format!("--time-limit={time_limit}"))
+            .arg("data/bsp1.graph")
+            .arg(std::env::temp_dir().join("thm_ptas_time_limit.js"))
+            .output()
+            .unwrap();
+        let stderr = String::from_utf8_lossy(&run.stderr);
+        assert_eq!(run.status.code(), Some(1), "{stderr}");
+        assert!(stderr.contains("time limit"), "{stderr}");
+        assert!(!stderr.contains("panicked"), "{stderr}");
+    }
+}